@@ -108,9 +108,15 @@ impl EstablishedConnection {
             incompatible: false,
             protocol: 1,
         };
-        let protocol = connection.get_proto_ver().get().await??;
-        connection.protocol = protocol;
-        connection.incompatible = protocol != VERSION.protocol;
+        if let Ok(handshake) = connection.handshake().get().await? {
+            connection.protocol = handshake.protocol.unwrap_or(handshake.max);
+            connection.incompatible = handshake.protocol.is_none();
+        } else {
+            // Controllers that predate the handshake only report their protocol version
+            let protocol = connection.get_proto_ver().get().await??;
+            connection.protocol = protocol;
+            connection.incompatible = protocol != VERSION.protocol;
+        }
 
         Ok(connection)
     }
@@ -128,15 +134,24 @@ impl EstablishedConnection {
     }
 
     fn create_request<T>(&self, data: T) -> Request<T> {
+        Self::authorize(&self.token, data)
+    }
+
+    // Used by tasks that send more than one request
+    fn authorize<T>(token: &str, data: T) -> Request<T> {
         let mut request = Request::new(data);
 
         // Add the token to the metadata
         request.metadata_mut().insert(
             "authorization",
-            self.token
+            token
                 .parse()
                 .expect("Failed to convert token to header value"),
         );
+        // Lets the controller keep the behaviour this version expects
+        request
+            .metadata_mut()
+            .insert("protocol-version", VERSION.protocol.into());
 
         request
     }
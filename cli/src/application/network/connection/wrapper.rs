@@ -3,13 +3,17 @@ use std::borrow::Cow;
 use color_eyre::eyre::Result;
 use tonic::Streaming;
 
-use crate::application::network::proto::{
-    common::{common_group, common_server, common_user, notify},
-    manage::{
-        group, node, plugin,
-        resource::{DelReq, SetReq},
-        screen, server,
-        transfer::TransferReq,
+use crate::{
+    VERSION,
+    application::network::proto::{
+        common::{Page, common_group, common_server, common_user, handshake, notify},
+        manage::{
+            action::{BroadcastReq, KickReq},
+            group, node, plugin,
+            resource::{DelReq, SetReq},
+            screen, server,
+            transfer::TransferReq,
+        },
     },
 };
 
@@ -163,16 +167,33 @@ impl EstablishedConnection {
 
     pub fn get_groups(&self) -> NetworkTask<Result<Vec<common_group::Short>>> {
         let connection = self.connection.clone();
-        let request = self.create_request(());
+        let token = self.token.clone();
 
         spawn(async move {
-            Ok(connection
-                .write()
-                .await
-                .get_groups(request)
-                .await?
-                .into_inner()
-                .groups)
+            // The controller limits the size of a page, so follow the pages until the end
+            let mut groups = vec![];
+            let mut page = Page::default();
+            loop {
+                let request = Self::authorize(
+                    &token,
+                    common_group::ListReq {
+                        page: Some(page.clone()),
+                        ..Default::default()
+                    },
+                );
+                let list = connection
+                    .write()
+                    .await
+                    .get_groups(request)
+                    .await?
+                    .into_inner();
+                groups.extend(list.groups);
+                match list.next {
+                    Some(next) => page.token = Some(next),
+                    None => break,
+                }
+            }
+            Ok(groups)
         })
     }
 
@@ -195,16 +216,33 @@ impl EstablishedConnection {
 
     pub fn get_servers(&self) -> NetworkTask<Result<Vec<common_server::Short>>> {
         let connection = self.connection.clone();
-        let request = self.create_request(());
+        let token = self.token.clone();
 
         spawn(async move {
-            Ok(connection
-                .write()
-                .await
-                .get_servers(request)
-                .await?
-                .into_inner()
-                .servers)
+            // The controller limits the size of a page, so follow the pages until the end
+            let mut servers = vec![];
+            let mut page = Page::default();
+            loop {
+                let request = Self::authorize(
+                    &token,
+                    common_server::ListReq {
+                        page: Some(page.clone()),
+                        ..Default::default()
+                    },
+                );
+                let list = connection
+                    .write()
+                    .await
+                    .get_servers(request)
+                    .await?
+                    .into_inner();
+                servers.extend(list.servers);
+                match list.next {
+                    Some(next) => page.token = Some(next),
+                    None => break,
+                }
+            }
+            Ok(servers)
         })
     }
 
@@ -223,7 +261,10 @@ impl EstablishedConnection {
         T: Into<Cow<'a, str>>,
     {
         let connection = self.connection.clone();
-        let request = self.create_request(id.into().into_owned());
+        let request = self.create_request(screen::SubscribeReq {
+            id: id.into().into_owned(),
+            ..Default::default()
+        });
 
         spawn(async move {
             Ok(connection
@@ -237,16 +278,33 @@ impl EstablishedConnection {
 
     pub fn get_users(&self) -> NetworkTask<Result<Vec<common_user::Item>>> {
         let connection = self.connection.clone();
-        let request = self.create_request(());
+        let token = self.token.clone();
 
         spawn(async move {
-            Ok(connection
-                .write()
-                .await
-                .get_users(request)
-                .await?
-                .into_inner()
-                .users)
+            // The controller limits the size of a page, so follow the pages until the end
+            let mut users = vec![];
+            let mut page = Page::default();
+            loop {
+                let request = Self::authorize(
+                    &token,
+                    common_user::ListReq {
+                        page: Some(page.clone()),
+                        ..Default::default()
+                    },
+                );
+                let list = connection
+                    .write()
+                    .await
+                    .get_users(request)
+                    .await?
+                    .into_inner();
+                users.extend(list.users);
+                match list.next {
+                    Some(next) => page.token = Some(next),
+                    None => break,
+                }
+            }
+            Ok(users)
         })
     }
 
@@ -260,6 +318,47 @@ impl EstablishedConnection {
         })
     }
 
+    pub fn kick_user(&self, request: KickReq) -> EmptyTask {
+        let connection = self.connection.clone();
+        let request = self.create_request(request);
+
+        spawn(async move {
+            connection.write().await.kick_user(request).await?;
+            Ok(())
+        })
+    }
+
+    pub fn broadcast_message(&self, request: BroadcastReq) -> NetworkTask<Result<u32>> {
+        let connection = self.connection.clone();
+        let request = self.create_request(request);
+
+        spawn(async move {
+            Ok(connection
+                .write()
+                .await
+                .broadcast_message(request)
+                .await?
+                .into_inner())
+        })
+    }
+
+    pub fn handshake(&self) -> NetworkTask<Result<handshake::Res>> {
+        let connection = self.connection.clone();
+        let request = self.create_request(handshake::Req {
+            protocol: VERSION.protocol,
+            capabilities: vec![],
+        });
+
+        spawn(async move {
+            Ok(connection
+                .write()
+                .await
+                .handshake(request)
+                .await?
+                .into_inner())
+        })
+    }
+
     pub fn get_proto_ver(&self) -> NetworkTask<Result<u32>> {
         let connection = self.connection.clone();
         let request = self.create_request(());
@@ -98,6 +98,7 @@ impl Window for BasicWindow<'_> {
                                                 resources: None,
                                                 scaling: None,
                                                 specification: None,
+                                                alerts: vec![],
                                             },
                                         ));
                                         Ok(())
@@ -202,6 +202,7 @@ impl Window for SpecificationWindow<'_> {
                             environment: environment.0,
                             retention: Some(retention as i32),
                             fallback: fallback.map(|fallback| Fallback { priority: fallback }),
+                            channels: None,
                         });
 
                         stack.pop(); // This is required to free the data stored in the struct
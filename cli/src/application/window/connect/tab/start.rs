@@ -29,7 +29,7 @@ use super::{
     group::{create::CreateGroupTab, get::GetGroupTab},
     node::{create::CreateNodeTab, get::GetNodeTab},
     server::{get::GetServerTab, screen::ScreenTab},
-    user::transfer::TransferUserTab,
+    user::{broadcast::BroadcastTab, kick::KickUserTab, transfer::TransferUserTab},
 };
 
 pub struct StartTab {
@@ -62,6 +62,10 @@ enum Action {
     // Transfer operations
     TransferUsers,
 
+    // Action operations
+    KickUsers,
+    Broadcast,
+
     // General
     RequestStop,
     GetVersions,
@@ -78,6 +82,8 @@ impl StartTab {
                     Action::SetResource,
                     Action::OpenScreen,
                     Action::TransferUsers,
+                    Action::KickUsers,
+                    Action::Broadcast,
                     Action::GetNode,
                     Action::GetGroup,
                     Action::GetServer,
@@ -167,6 +173,17 @@ impl Window for StartTab {
                                 TransferUserTab::new_stack(self.connection.clone()),
                             ),
 
+                            Action::KickUsers => stack.add_tab(
+                                "Kick",
+                                RED,
+                                KickUserTab::new_stack(self.connection.clone()),
+                            ),
+                            Action::Broadcast => stack.add_tab(
+                                "Broadcast",
+                                BLUE,
+                                BroadcastTab::new_stack(self.connection.clone()),
+                            ),
+
                             Action::RequestStop => {
                                 stack.add_tab(
                                     "Stop",
@@ -222,10 +239,10 @@ impl From<&Action> for ListItem<'_> {
             Action::CreateGroup | Action::CreateNode | Action::SetResource => {
                 ListItem::new(Line::styled(format!(" {action}"), OK_SELECTED_COLOR))
             }
-            Action::DeleteResource | Action::RequestStop => {
+            Action::DeleteResource | Action::RequestStop | Action::KickUsers => {
                 ListItem::new(Line::styled(format!(" {action}"), ERROR_SELECTED_COLOR))
             }
-            Action::OpenScreen | Action::TransferUsers => {
+            Action::OpenScreen | Action::TransferUsers | Action::Broadcast => {
                 ListItem::new(Line::styled(format!(" {action}"), INFO_SELECTED_COLOR))
             }
             _ => ListItem::new(Line::styled(format!(" {action}"), TEXT_FG_COLOR)),
@@ -251,6 +268,9 @@ impl Display for Action {
 
             Action::TransferUsers => write!(formatter, "Transfer a users to a different Server"),
 
+            Action::KickUsers => write!(formatter, "Kick users from the network"),
+            Action::Broadcast => write!(formatter, "Broadcast a message to every Server"),
+
             Action::RequestStop => write!(formatter, "Request stop of Controller"),
             Action::GetVersions => write!(formatter, "Get versions"),
         }
@@ -1 +1,3 @@
+pub mod broadcast;
+pub mod kick;
 pub mod transfer;
@@ -0,0 +1,218 @@
+use std::sync::Arc;
+
+use color_eyre::eyre::Result;
+use crossterm::event::{Event, KeyCode, KeyEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    widgets::{Paragraph, Widget},
+};
+use tonic::async_trait;
+
+use crate::application::{
+    State,
+    network::{
+        connection::{EstablishedConnection, task::NetworkTask},
+        proto::manage::action::BroadcastReq,
+    },
+    util::{
+        area::SimpleTextArea,
+        status::{Status, StatusDisplay},
+    },
+    window::{StackBatcher, Window, WindowUtils, connect::tab::global::delete::AUTO_CLOSE_AFTER},
+};
+
+pub struct BroadcastTab {
+    /* Network */
+    request: NetworkTask<Result<u32>>,
+
+    /* Window */
+    status: StatusDisplay,
+}
+
+pub struct MessageWindow<'a> {
+    /* Connection */
+    connection: Arc<EstablishedConnection>,
+
+    /* Window */
+    message: SimpleTextArea<'a, ()>,
+}
+
+impl BroadcastTab {
+    /// Creates a new broadcast tab.
+    /// This function will create a window stack to get the message that should be broadcasted.
+    pub fn new_stack(connection: Arc<EstablishedConnection>) -> MessageWindow<'static> {
+        MessageWindow::new(connection)
+    }
+
+    pub fn new(connection: &Arc<EstablishedConnection>, message: String) -> Self {
+        Self {
+            request: connection.broadcast_message(BroadcastReq {
+                message,
+                group: None,
+            }),
+            status: StatusDisplay::new_with_startpoint(Status::Loading, "Broadcasting message..."),
+        }
+    }
+}
+
+impl MessageWindow<'_> {
+    pub fn new(connection: Arc<EstablishedConnection>) -> Self {
+        Self {
+            connection,
+            message: SimpleTextArea::new_selected(
+                (),
+                "Message",
+                "Please enter the message every server should display",
+                SimpleTextArea::not_empty_validation,
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl Window for BroadcastTab {
+    async fn init(&mut self, _stack: &mut StackBatcher, _state: &mut State) -> Result<()> {
+        Ok(())
+    }
+
+    async fn tick(&mut self, stack: &mut StackBatcher, _state: &mut State) -> Result<()> {
+        // Network connection
+        match self.request.get_now().await {
+            Ok(Some(Ok(servers))) => {
+                self.status.change_with_startpoint(
+                    Status::Successful,
+                    format!("Sucessfully broadcasted message to {servers} server/s"),
+                );
+            }
+            Err(error) | Ok(Some(Err(error))) => {
+                self.status
+                    .change(Status::Fatal, format!("{}", error.root_cause()));
+            }
+            _ => {}
+        }
+
+        // UI
+        self.status.next();
+        if self.status.is_successful() && self.status.elapsed() > AUTO_CLOSE_AFTER {
+            stack.close_tab();
+        }
+        Ok(())
+    }
+
+    async fn handle_event(
+        &mut self,
+        stack: &mut StackBatcher,
+        _state: &mut State,
+        event: Event,
+    ) -> Result<()> {
+        if let Event::Key(event) = event {
+            if event.kind != KeyEventKind::Press {
+                return Ok(());
+            }
+            if event.code == KeyCode::Esc {
+                self.request.abort();
+                stack.close_tab();
+            }
+        }
+        Ok(())
+    }
+
+    fn render(&mut self, area: Rect, buffer: &mut Buffer) {
+        Widget::render(self, area, buffer);
+    }
+}
+
+#[async_trait]
+impl Window for MessageWindow<'_> {
+    async fn init(&mut self, _stack: &mut StackBatcher, _state: &mut State) -> Result<()> {
+        Ok(())
+    }
+
+    async fn tick(&mut self, _stack: &mut StackBatcher, _state: &mut State) -> Result<()> {
+        Ok(())
+    }
+
+    async fn handle_event(
+        &mut self,
+        stack: &mut StackBatcher,
+        _state: &mut State,
+        event: Event,
+    ) -> Result<()> {
+        if let Event::Key(event) = event {
+            if event.kind != KeyEventKind::Press {
+                return Ok(());
+            }
+            match event.code {
+                KeyCode::Esc => stack.close_tab(),
+                KeyCode::Enter => {
+                    if self.message.is_valid() {
+                        let message = self.message.get_first_line();
+                        stack.pop(); // This is required to free the data stored in the struct
+                        stack.push(BroadcastTab::new(&self.connection, message));
+                    }
+                }
+                _ => self.message.handle_event(event),
+            }
+        }
+        Ok(())
+    }
+
+    fn render(&mut self, area: Rect, buffer: &mut Buffer) {
+        Widget::render(self, area, buffer);
+    }
+}
+
+impl Widget for &mut BroadcastTab {
+    fn render(self, area: Rect, buffer: &mut Buffer) {
+        let [main_area, footer_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+
+        BroadcastTab::render_footer(footer_area, buffer);
+
+        self.render_body(main_area, buffer);
+    }
+}
+
+impl BroadcastTab {
+    fn render_footer(area: Rect, buffer: &mut Buffer) {
+        Paragraph::new("Esc to close tab.")
+            .centered()
+            .render(area, buffer);
+    }
+
+    fn render_body(&mut self, area: Rect, buffer: &mut Buffer) {
+        self.status.render_in_center(area, buffer);
+    }
+}
+
+impl Widget for &mut MessageWindow<'_> {
+    fn render(self, area: Rect, buffer: &mut Buffer) {
+        let [title_area, main_area, footer_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        WindowUtils::render_tab_header("Broadcast message", title_area, buffer);
+        MessageWindow::render_footer(footer_area, buffer);
+
+        self.render_body(main_area, buffer);
+    }
+}
+
+impl MessageWindow<'_> {
+    fn render_footer(area: Rect, buffer: &mut Buffer) {
+        Paragraph::new("Use ↵ to confirm, Esc to close tab.")
+            .centered()
+            .render(area, buffer);
+    }
+
+    fn render_body(&mut self, area: Rect, buffer: &mut Buffer) {
+        let [message_area, _] =
+            Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(area);
+
+        self.message.render(message_area, buffer);
+    }
+}
@@ -0,0 +1,286 @@
+use std::sync::Arc;
+
+use color_eyre::eyre::Result;
+use crossterm::event::{Event, KeyCode, KeyEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    widgets::{Paragraph, Widget},
+};
+use tokio::time::Instant;
+use tonic::async_trait;
+
+use crate::application::{
+    State,
+    network::{
+        connection::{EstablishedConnection, task::EmptyTask},
+        proto::{common::common_user, manage::action::KickReq},
+    },
+    util::{
+        area::SimpleTextArea,
+        status::{Status, StatusDisplay},
+    },
+    window::{
+        StackBatcher, Window, WindowUtils,
+        connect::tab::{
+            global::delete::AUTO_CLOSE_AFTER,
+            util::{fetch::FetchWindow, multi_select::MultiSelectWindow},
+        },
+    },
+};
+
+pub struct KickUserTab {
+    /* Network */
+    requests: Vec<(StatusDisplay, EmptyTask)>,
+
+    /* Window */
+    finished: Option<Instant>,
+}
+
+pub struct ReasonWindow<'a> {
+    /* Connection */
+    connection: Arc<EstablishedConnection>,
+    users: Vec<common_user::Item>,
+
+    /* Window */
+    reason: SimpleTextArea<'a, ()>,
+}
+
+impl KickUserTab {
+    /// Creates a new kick user tab.
+    /// This function will create a window stack to get the required information to kick the users.
+    pub fn new_stack(
+        connection: Arc<EstablishedConnection>,
+    ) -> FetchWindow<Vec<common_user::Item>> {
+        FetchWindow::new(
+            connection.get_users(),
+            connection,
+            move |users, connection: Arc<EstablishedConnection>, stack, _| {
+                stack.push(MultiSelectWindow::new(
+                    "Select the user/s you want to kick",
+                    users,
+                    move |users, stack, _| {
+                        stack.push(ReasonWindow::new(connection, users));
+                        Ok(())
+                    },
+                ));
+                Ok(())
+            },
+        )
+    }
+
+    pub fn new<T>(connection: &Arc<EstablishedConnection>, users: T, reason: Option<&str>) -> Self
+    where
+        T: IntoIterator<Item = common_user::Item>,
+    {
+        Self {
+            requests: users
+                .into_iter()
+                .map(|user| {
+                    (
+                        StatusDisplay::new_with_startpoint(
+                            Status::Loading,
+                            format!("Kicking user {}...", user.name),
+                        ),
+                        connection.kick_user(KickReq {
+                            user: user.id,
+                            reason: reason.map(str::to_string),
+                        }),
+                    )
+                })
+                .collect(),
+            finished: None,
+        }
+    }
+}
+
+impl ReasonWindow<'_> {
+    pub fn new(connection: Arc<EstablishedConnection>, users: Vec<common_user::Item>) -> Self {
+        Self {
+            connection,
+            users,
+            reason: SimpleTextArea::new_selected(
+                (),
+                "Reason",
+                "Optionally enter the reason shown to the user/s",
+                |_, ()| Ok(()),
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl Window for KickUserTab {
+    async fn init(&mut self, _stack: &mut StackBatcher, _state: &mut State) -> Result<()> {
+        Ok(())
+    }
+
+    async fn tick(&mut self, stack: &mut StackBatcher, _state: &mut State) -> Result<()> {
+        for (status, request) in &mut self.requests {
+            // Network connection
+            match request.get_now().await {
+                Ok(Some(Ok(()))) => {
+                    status.change_with_startpoint(Status::Successful, "Sucessfully kicked user");
+                }
+                Err(error) | Ok(Some(Err(error))) => {
+                    status.change(Status::Fatal, format!("{}", error.root_cause()));
+                }
+                _ => {}
+            }
+
+            // UI
+            status.next();
+        }
+
+        if self
+            .requests
+            .iter()
+            .all(|(status, _)| status.is_successful())
+        {
+            if let Some(instant) = &self.finished {
+                if instant.elapsed() >= AUTO_CLOSE_AFTER {
+                    stack.close_tab();
+                }
+            } else {
+                self.finished = Some(Instant::now());
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn handle_event(
+        &mut self,
+        stack: &mut StackBatcher,
+        _state: &mut State,
+        event: Event,
+    ) -> Result<()> {
+        if let Event::Key(event) = event {
+            if event.kind != KeyEventKind::Press {
+                return Ok(());
+            }
+            if event.code == KeyCode::Esc {
+                for (_, request) in &mut self.requests {
+                    request.abort();
+                }
+                stack.close_tab();
+            }
+        }
+        Ok(())
+    }
+
+    fn render(&mut self, area: Rect, buffer: &mut Buffer) {
+        Widget::render(self, area, buffer);
+    }
+}
+
+#[async_trait]
+impl Window for ReasonWindow<'_> {
+    async fn init(&mut self, _stack: &mut StackBatcher, _state: &mut State) -> Result<()> {
+        Ok(())
+    }
+
+    async fn tick(&mut self, _stack: &mut StackBatcher, _state: &mut State) -> Result<()> {
+        Ok(())
+    }
+
+    async fn handle_event(
+        &mut self,
+        stack: &mut StackBatcher,
+        _state: &mut State,
+        event: Event,
+    ) -> Result<()> {
+        if let Event::Key(event) = event {
+            if event.kind != KeyEventKind::Press {
+                return Ok(());
+            }
+            match event.code {
+                KeyCode::Esc => stack.close_tab(),
+                KeyCode::Enter => {
+                    let reason = self.reason.get_first_line();
+                    stack.pop(); // This is required to free the data stored in the struct
+                    stack.push(KickUserTab::new(
+                        &self.connection,
+                        self.users.drain(..),
+                        Some(reason.as_str()).filter(|reason| !reason.is_empty()),
+                    ));
+                }
+                _ => self.reason.handle_event(event),
+            }
+        }
+        Ok(())
+    }
+
+    fn render(&mut self, area: Rect, buffer: &mut Buffer) {
+        Widget::render(self, area, buffer);
+    }
+}
+
+impl Widget for &mut KickUserTab {
+    fn render(self, area: Rect, buffer: &mut Buffer) {
+        let [main_area, footer_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+
+        KickUserTab::render_footer(footer_area, buffer);
+
+        self.render_body(main_area, buffer);
+    }
+}
+
+impl KickUserTab {
+    fn render_footer(area: Rect, buffer: &mut Buffer) {
+        Paragraph::new("Esc to close tab.")
+            .centered()
+            .render(area, buffer);
+    }
+
+    fn render_body(&mut self, area: Rect, buffer: &mut Buffer) {
+        // Create a layout with the same number of areas as the requests + 2 for the top and bottom
+        let mut layout = Vec::with_capacity(self.requests.len() + 2);
+        layout.push(Constraint::Fill(1));
+        layout.extend(std::iter::repeat_n(
+            Constraint::Length(1),
+            self.requests.len(),
+        ));
+        layout.push(Constraint::Fill(1));
+
+        let areas = Layout::vertical(layout).split(area);
+        self.requests
+            .iter()
+            .enumerate()
+            .for_each(|(i, (status, _))| {
+                status.render_in_center(areas[i + 1], buffer);
+            });
+    }
+}
+
+impl Widget for &mut ReasonWindow<'_> {
+    fn render(self, area: Rect, buffer: &mut Buffer) {
+        let [title_area, main_area, footer_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        WindowUtils::render_tab_header("Kick reason", title_area, buffer);
+        ReasonWindow::render_footer(footer_area, buffer);
+
+        self.render_body(main_area, buffer);
+    }
+}
+
+impl ReasonWindow<'_> {
+    fn render_footer(area: Rect, buffer: &mut Buffer) {
+        Paragraph::new("Use ↵ to confirm, Esc to close tab.")
+            .centered()
+            .render(area, buffer);
+    }
+
+    fn render_body(&mut self, area: Rect, buffer: &mut Buffer) {
+        let [reason_area, _] =
+            Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(area);
+
+        self.reason.render(reason_area, buffer);
+    }
+}
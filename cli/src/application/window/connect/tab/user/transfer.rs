@@ -125,6 +125,7 @@ impl TransferUserTab {
                     r#type: typ as i32,
                     target,
                 }),
+                together: false,
             }),
             status: StatusDisplay::new_with_startpoint(Status::Loading, "Submitting request..."),
         }
@@ -4,14 +4,18 @@ import com.google.common.util.concurrent.FutureCallback;
 import com.google.common.util.concurrent.Futures;
 import com.google.common.util.concurrent.ListenableFuture;
 import io.atomic.cloud.grpc.client.*;
+import io.atomic.cloud.grpc.common.Page;
 import io.grpc.*;
 import java.io.ByteArrayInputStream;
 import java.io.IOException;
 import java.net.URL;
 import java.nio.charset.StandardCharsets;
+import java.util.Optional;
 import java.util.concurrent.CompletableFuture;
 import java.util.concurrent.Executor;
 import java.util.concurrent.Executors;
+import java.util.function.BinaryOperator;
+import java.util.function.Function;
 import lombok.Getter;
 import lombok.RequiredArgsConstructor;
 import org.jetbrains.annotations.NotNull;
@@ -64,4 +68,27 @@ public abstract class Connection {
                 EXECUTOR);
         return newFuture;
     }
+
+    // The controller limits the size of every page, so list calls follow the pages until the end
+    protected <T> @NotNull CompletableFuture<T> allPages(
+            @NotNull Function<Page, ListenableFuture<T>> request,
+            @NotNull Function<T, Optional<String>> next,
+            @NotNull BinaryOperator<T> merge) {
+        return this.allPages(request, next, merge, Page.getDefaultInstance());
+    }
+
+    private <T> @NotNull CompletableFuture<T> allPages(
+            @NotNull Function<Page, ListenableFuture<T>> request,
+            @NotNull Function<T, Optional<String>> next,
+            @NotNull BinaryOperator<T> merge,
+            @NotNull Page page) {
+        return this.wrapInFuture(request.apply(page)).thenCompose(value -> next.apply(value)
+                .map(token -> this.allPages(
+                                request,
+                                next,
+                                merge,
+                                page.toBuilder().setToken(token).build())
+                        .thenApply(rest -> merge.apply(value, rest)))
+                .orElseGet(() -> CompletableFuture.completedFuture(value)));
+    }
 }
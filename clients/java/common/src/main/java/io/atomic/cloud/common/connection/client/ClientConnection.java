@@ -18,6 +18,7 @@ import java.net.URI;
 import java.net.URISyntaxException;
 import java.net.URL;
 import java.util.Optional;
+import java.util.UUID;
 import java.util.concurrent.CompletableFuture;
 import org.jetbrains.annotations.Contract;
 import org.jetbrains.annotations.NotNull;
@@ -71,14 +72,41 @@ public class ClientConnection extends Connection {
         return super.wrapInFuture(this.futureClient.userDisconnected(user));
     }
 
+    public CompletableFuture<Empty> setUserData(User.SetDataReq data) {
+        return super.wrapInFuture(this.futureClient.setUserData(data));
+    }
+
+    public CompletableFuture<User.Data> userData(User.GetDataReq request) {
+        return super.wrapInFuture(this.futureClient.getUserData(request));
+    }
+
     public CompletableFuture<UInt32Value> transferUsers(Transfer.TransferReq transfer) {
         return super.wrapInFuture(this.futureClient.transferUsers(transfer));
     }
 
+    public CallHandle<?, CommonTransfer.Status> transferUsersWithStatus(
+            Transfer.TransferReq transfer, StreamObserver<CommonTransfer.Status> observer) {
+        var handle = new CallHandle<>(observer);
+        this.client.transferUsersWithStatus(transfer, handle);
+        return handle;
+    }
+
     public CompletableFuture<UInt32Value> publishMessage(Channel.Msg message) {
         return super.wrapInFuture(this.futureClient.publishMessage(message));
     }
 
+    public CompletableFuture<Empty> acknowledgeMessages(Channel.AckReq ack) {
+        return super.wrapInFuture(this.futureClient.acknowledgeMessages(ack));
+    }
+
+    public CompletableFuture<Channel.Reply> requestOnChannel(Channel.Request request) {
+        return super.wrapInFuture(this.futureClient.requestOnChannel(request));
+    }
+
+    public CompletableFuture<BoolValue> replyToRequest(Channel.Reply reply) {
+        return super.wrapInFuture(this.futureClient.replyToRequest(reply));
+    }
+
     public CompletableFuture<CommonUser.Item> user(String server) {
         return super.wrapInFuture(this.futureClient.getUser(StringValue.of(server)));
     }
@@ -87,18 +115,22 @@ public class ClientConnection extends Connection {
         return super.wrapInFuture(this.futureClient.getUserFromName(StringValue.of(server)));
     }
 
-    public CompletableFuture<CommonGroup.Short> group(String group) {
+    public CompletableFuture<Group.Detail> group(String group) {
         return super.wrapInFuture(this.futureClient.getGroup(StringValue.of(group)));
     }
 
-    public CompletableFuture<CommonServer.Short> server(String server) {
+    public CompletableFuture<Server.Detail> server(String server) {
         return super.wrapInFuture(this.futureClient.getServer(StringValue.of(server)));
     }
 
-    public CompletableFuture<CommonServer.Short> serverFromName(String server) {
+    public CompletableFuture<Server.Detail> serverFromName(String server) {
         return super.wrapInFuture(this.futureClient.getServerFromName(StringValue.of(server)));
     }
 
+    public CompletableFuture<Server.Detail> resolveReconnect(UUID user) {
+        return super.wrapInFuture(this.futureClient.resolveReconnect(StringValue.of(user.toString())));
+    }
+
     public synchronized Optional<CommonUser.List> usersNow() {
         var cached = this.usersInfo.getValue();
         if (cached.isEmpty()) {
@@ -108,8 +140,14 @@ public class ClientConnection extends Connection {
     }
 
     public synchronized CompletableFuture<CommonUser.List> users() {
-        return this.usersInfo.getValue().map(CompletableFuture::completedFuture).orElseGet(() -> super.wrapInFuture(
-                        this.futureClient.getUsers(Empty.getDefaultInstance()))
+        return this.usersInfo.getValue().map(CompletableFuture::completedFuture).orElseGet(() -> super.allPages(
+                        page -> this.futureClient.getUsers(
+                                CommonUser.ListReq.newBuilder().setPage(page).build()),
+                        value -> value.hasNext() ? Optional.of(value.getNext()) : Optional.empty(),
+                        (first, rest) -> first.toBuilder()
+                                .addAllUsers(rest.getUsersList())
+                                .clearNext()
+                                .build())
                 .thenApply((value) -> {
                     this.usersInfo.setValue(value);
                     return value;
@@ -145,7 +183,14 @@ public class ClientConnection extends Connection {
         return this.serversInfo
                 .getValue()
                 .map(CompletableFuture::completedFuture)
-                .orElseGet(() -> super.wrapInFuture(this.futureClient.getServers(Empty.getDefaultInstance()))
+                .orElseGet(() -> super.allPages(
+                                page -> this.futureClient.getServers(
+                                        CommonServer.ListReq.newBuilder().setPage(page).build()),
+                                value -> value.hasNext() ? Optional.of(value.getNext()) : Optional.empty(),
+                                (first, rest) -> first.toBuilder()
+                                        .addAllServers(rest.getServersList())
+                                        .clearNext()
+                                        .build())
                         .thenApply((value) -> {
                             this.serversInfo.setValue(value);
                             return value;
@@ -164,7 +209,14 @@ public class ClientConnection extends Connection {
         return this.groupsInfo
                 .getValue()
                 .map(CompletableFuture::completedFuture)
-                .orElseGet(() -> super.wrapInFuture(this.futureClient.getGroups(Empty.getDefaultInstance()))
+                .orElseGet(() -> super.allPages(
+                                page -> this.futureClient.getGroups(
+                                        CommonGroup.ListReq.newBuilder().setPage(page).build()),
+                                value -> value.hasNext() ? Optional.of(value.getNext()) : Optional.empty(),
+                                (first, rest) -> first.toBuilder()
+                                        .addAllGroups(rest.getGroupsList())
+                                        .clearNext()
+                                        .build())
                         .thenApply((value) -> {
                             this.groupsInfo.setValue(value);
                             return value;
@@ -200,9 +252,35 @@ public class ClientConnection extends Connection {
         return handle;
     }
 
+    public CallHandle<?, Transfer.QueueUpdate> subscribeToQueueUpdates(
+            StreamObserver<Transfer.QueueUpdate> observer) {
+        var handle = new CallHandle<>(observer);
+        this.client.subscribeToQueueUpdates(Empty.getDefaultInstance(), handle);
+        return handle;
+    }
+
+    public CallHandle<?, Action> subscribeToActions(StreamObserver<Action> observer) {
+        var handle = new CallHandle<>(observer);
+        this.client.subscribeToActions(Empty.getDefaultInstance(), handle);
+        return handle;
+    }
+
     public CallHandle<?, Channel.Msg> subscribeToChannel(String channel, StreamObserver<Channel.Msg> observer) {
+        return this.subscribeToChannel(
+                Channel.SubscribeReq.newBuilder().setChannel(channel).build(), observer);
+    }
+
+    public CallHandle<?, Channel.Msg> subscribeToChannel(
+            Channel.SubscribeReq request, StreamObserver<Channel.Msg> observer) {
+        var handle = new CallHandle<>(observer);
+        this.client.subscribeToChannel(request, handle);
+        return handle;
+    }
+
+    public CallHandle<?, Channel.Request> subscribeToRequests(
+            String channel, StreamObserver<Channel.Request> observer) {
         var handle = new CallHandle<>(observer);
-        this.client.subscribeToChannel(StringValue.of(channel), handle);
+        this.client.subscribeToRequests(StringValue.of(channel), handle);
         return handle;
     }
 
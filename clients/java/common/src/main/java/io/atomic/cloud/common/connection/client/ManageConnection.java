@@ -9,6 +9,7 @@ import io.atomic.cloud.common.connection.call.CallHandle;
 import io.atomic.cloud.common.connection.credential.TokenCredential;
 import io.atomic.cloud.grpc.common.CommonGroup;
 import io.atomic.cloud.grpc.common.CommonServer;
+import io.atomic.cloud.grpc.common.CommonTransfer;
 import io.atomic.cloud.grpc.common.CommonUser;
 import io.atomic.cloud.grpc.common.Notify;
 import io.atomic.cloud.grpc.manage.*;
@@ -79,6 +80,13 @@ public class ManageConnection extends Connection {
         return super.wrapInFuture(this.futureClient.transferUsers(request));
     }
 
+    public CallHandle<?, CommonTransfer.Status> transferUsersWithStatus(
+            Transfer.TransferReq request, StreamObserver<CommonTransfer.Status> observer) {
+        var handle = new CallHandle<>(observer);
+        this.client.transferUsersWithStatus(request, handle);
+        return handle;
+    }
+
     public CompletableFuture<Node.Detail> node(String node) {
         return super.wrapInFuture(this.futureClient.getNode(StringValue.of(node)));
     }
@@ -168,7 +176,14 @@ public class ManageConnection extends Connection {
         return this.groupsInfo
                 .getValue()
                 .map(CompletableFuture::completedFuture)
-                .orElseGet(() -> super.wrapInFuture(this.futureClient.getGroups(Empty.getDefaultInstance()))
+                .orElseGet(() -> super.allPages(
+                                page -> this.futureClient.getGroups(
+                                        CommonGroup.ListReq.newBuilder().setPage(page).build()),
+                                value -> value.hasNext() ? Optional.of(value.getNext()) : Optional.empty(),
+                                (first, rest) -> first.toBuilder()
+                                        .addAllGroups(rest.getGroupsList())
+                                        .clearNext()
+                                        .build())
                         .thenApply((value) -> {
                             this.groupsInfo.setValue(value);
                             return value;
@@ -187,7 +202,14 @@ public class ManageConnection extends Connection {
         return this.serversInfo
                 .getValue()
                 .map(CompletableFuture::completedFuture)
-                .orElseGet(() -> super.wrapInFuture(this.futureClient.getServers(Empty.getDefaultInstance()))
+                .orElseGet(() -> super.allPages(
+                                page -> this.futureClient.getServers(
+                                        CommonServer.ListReq.newBuilder().setPage(page).build()),
+                                value -> value.hasNext() ? Optional.of(value.getNext()) : Optional.empty(),
+                                (first, rest) -> first.toBuilder()
+                                        .addAllServers(rest.getServersList())
+                                        .clearNext()
+                                        .build())
                         .thenApply((value) -> {
                             this.serversInfo.setValue(value);
                             return value;
@@ -203,8 +225,14 @@ public class ManageConnection extends Connection {
     }
 
     public synchronized CompletableFuture<CommonUser.List> users() {
-        return this.usersInfo.getValue().map(CompletableFuture::completedFuture).orElseGet(() -> super.wrapInFuture(
-                        this.futureClient.getUsers(Empty.getDefaultInstance()))
+        return this.usersInfo.getValue().map(CompletableFuture::completedFuture).orElseGet(() -> super.allPages(
+                        page -> this.futureClient.getUsers(
+                                CommonUser.ListReq.newBuilder().setPage(page).build()),
+                        value -> value.hasNext() ? Optional.of(value.getNext()) : Optional.empty(),
+                        (first, rest) -> first.toBuilder()
+                                .addAllUsers(rest.getUsersList())
+                                .clearNext()
+                                .build())
                 .thenApply((value) -> {
                     this.usersInfo.setValue(value);
                     return value;
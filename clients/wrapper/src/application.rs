@@ -7,18 +7,20 @@ use std::{
     time::Duration,
 };
 
+use action::Actions;
 use anyhow::Result;
 use detection::RegexDetector;
 use heart::Heart;
 use network::CloudConnection;
 use process::ManagedProcess;
-use simplelog::{error, info};
+use simplelog::{error, info, warn};
 use tokio::select;
 use transfer::Transfers;
 use user::Users;
 
-use crate::args::Args;
+use crate::{VERSION, args::Args};
 
+mod action;
 mod detection;
 mod heart;
 mod network;
@@ -42,6 +44,7 @@ pub struct Wrapper {
     heart: Heart,
     users: Users,
     transfers: Transfers,
+    actions: Actions,
     connection: Arc<CloudConnection>,
 }
 
@@ -59,6 +62,14 @@ impl Wrapper {
             error!("Failed to connect to cloud: {}", error);
             exit(1);
         }
+        match connection.handshake().await {
+            Ok(handshake) if handshake.protocol.is_none() => warn!(
+                "The controller only supports protocol versions {} to {} but this wrapper uses {}",
+                handshake.min, handshake.max, VERSION.protocol
+            ),
+            Ok(_) => {}
+            Err(error) => warn!("Failed to negotiate protocol version: {}", error),
+        }
 
         Self {
             program,
@@ -68,6 +79,7 @@ impl Wrapper {
             heart: Heart::new(BEAT_INTERVAL, connection.clone()),
             users: Users::new(connection.clone()),
             transfers: Transfers::from_env(connection.clone()),
+            actions: Actions::from_env(connection.clone()),
             connection,
         }
     }
@@ -88,6 +100,7 @@ impl Wrapper {
 
         // Subscribe to network events
         self.transfers.subscribe().await;
+        self.actions.subscribe().await;
 
         while self.running.load(Ordering::Relaxed) {
             self.tick().await;
@@ -111,6 +124,7 @@ impl Wrapper {
         if let Some(process) = &mut self.process {
             select! {
                 message = self.transfers.next_message() => self.transfers.handle_message(message, &mut process.stdin, &self.users).await,
+                message = self.actions.next_message() => self.actions.handle_message(message, &mut process.stdin, &self.users).await,
                 () = self.heart.wait_for_beat() => self.heart.beat().await,
                 result = process.process.wait() => if process.handle_process_exit(result).await { self.request_stop() },
                 line = process.stdout.next_line() => process.handle_stdout_line(line, &mut self.users).await,
@@ -0,0 +1,138 @@
+use std::str::FromStr;
+
+use simplelog::{error, info, warn};
+use tonic::Streaming;
+use uuid::Uuid;
+
+use super::{
+    network::{
+        CloudConnectionHandle,
+        proto::manage::{
+            Action,
+            action::{Broadcast, Kick, Kind},
+        },
+    },
+    process::stdin::ManagedStdin,
+    user::Users,
+};
+
+pub struct Actions {
+    /* Network */
+    connection: CloudConnectionHandle,
+
+    /* Stream */
+    stream: Option<Streaming<Action>>,
+
+    /* Action Commands */
+    kick_command: Option<String>,
+    broadcast_command: Option<String>,
+}
+
+impl Actions {
+    pub fn from_env(connection: CloudConnectionHandle) -> Self {
+        let kick_command = std::env::var("KICK_COMMAND").ok();
+        if kick_command.is_none() {
+            warn!("Missing KICK_COMMAND environment variable. Kick actions will be ignored");
+        }
+
+        let broadcast_command = std::env::var("BROADCAST_COMMAND").ok();
+        if broadcast_command.is_none() {
+            warn!(
+                "Missing BROADCAST_COMMAND environment variable. Broadcast actions will be ignored"
+            );
+        }
+
+        Self::new(connection, kick_command, broadcast_command)
+    }
+
+    pub const fn new(
+        connection: CloudConnectionHandle,
+        kick_command: Option<String>,
+        broadcast_command: Option<String>,
+    ) -> Self {
+        Self {
+            connection,
+            stream: None,
+            kick_command,
+            broadcast_command,
+        }
+    }
+
+    pub async fn subscribe(&mut self) {
+        match self.connection.subscribe_to_actions().await {
+            Ok(stream) => {
+                self.stream = Some(stream.into_inner());
+            }
+            Err(error) => {
+                error!("Failed to subscribe to actions: {}", error);
+            }
+        }
+    }
+
+    pub async fn next_message(&mut self) -> Option<Action> {
+        if let Some(stream) = &mut self.stream
+            && let Ok(result) = stream.message().await
+        {
+            return result;
+        }
+        None
+    }
+
+    pub async fn handle_message(
+        &self,
+        message: Option<Action>,
+        stdin: &mut ManagedStdin,
+        users: &Users,
+    ) {
+        match message.and_then(|action| action.kind) {
+            Some(Kind::Kick(kick)) => self.handle_kick(kick, stdin, users).await,
+            Some(Kind::Broadcast(broadcast)) => self.handle_broadcast(broadcast, stdin).await,
+            None => {}
+        }
+    }
+
+    async fn handle_kick(&self, kick: Kick, stdin: &mut ManagedStdin, users: &Users) {
+        let Some(command) = &self.kick_command else {
+            return;
+        };
+        let Ok(uuid) = Uuid::from_str(&kick.user) else {
+            error!("Failed to parse uuid: {}", kick.user);
+            return;
+        };
+        let Some(user) = users.get_user_from_uuid(uuid) else {
+            error!("Received kick for unknown user: {}", kick.user);
+            return;
+        };
+
+        info!("Kicking user {}", user.name);
+        let command = command
+            .replace("%NAME%", &user.name)
+            .replace("%UUID%", &user.uuid.to_string())
+            .replace(
+                "%REASON%",
+                &strip_control_characters(kick.reason.as_deref().unwrap_or_default()),
+            );
+        stdin.write_line(&command).await;
+    }
+
+    async fn handle_broadcast(&self, broadcast: Broadcast, stdin: &mut ManagedStdin) {
+        let Some(command) = &self.broadcast_command else {
+            return;
+        };
+
+        info!("Broadcasting message: {}", broadcast.message);
+        stdin
+            .write_line(
+                &command.replace("%MESSAGE%", &strip_control_characters(&broadcast.message)),
+            )
+            .await;
+    }
+}
+
+// A line break would let the sender run a second console command
+fn strip_control_characters(value: &str) -> String {
+    value
+        .chars()
+        .filter(|character| !character.is_control())
+        .collect()
+}
@@ -1,10 +1,14 @@
 use std::{process::exit, sync::Arc};
 
 use anyhow::Result;
-use proto::manage::{
-    client_service_client::ClientServiceClient,
-    transfer::TransferRes,
-    user::{ConnectedReq, DisconnectedReq},
+use proto::{
+    common::handshake,
+    manage::{
+        Action,
+        client_service_client::ClientServiceClient,
+        transfer::TransferRes,
+        user::{ConnectedReq, DisconnectedReq},
+    },
 };
 use simplelog::error;
 use tokio::sync::Mutex;
@@ -15,6 +19,8 @@ use tonic::{
     transport::{Certificate, Channel, ClientTlsConfig},
 };
 
+use crate::VERSION;
+
 pub mod proto {
     pub mod manage {
         #![allow(dead_code, clippy::all, clippy::pedantic)]
@@ -111,6 +117,23 @@ impl CloudConnection {
         Ok(())
     }
 
+    pub async fn handshake(&self) -> Result<handshake::Res> {
+        let request = self.create_request(handshake::Req {
+            protocol: VERSION.protocol,
+            capabilities: vec![],
+        });
+
+        Ok(self
+            .client
+            .lock()
+            .await
+            .as_mut()
+            .expect("No connection created")
+            .handshake(request)
+            .await?
+            .into_inner())
+    }
+
     pub async fn beat(&self) -> Result<()> {
         let request = self.create_request(());
 
@@ -201,6 +224,18 @@ impl CloudConnection {
             .await
     }
 
+    pub async fn subscribe_to_actions(&self) -> Result<Response<Streaming<Action>>, Status> {
+        let request = self.create_request(());
+
+        self.client
+            .lock()
+            .await
+            .as_mut()
+            .expect("No connection created")
+            .subscribe_to_actions(request)
+            .await
+    }
+
     fn create_request<T>(&self, data: T) -> Request<T> {
         let mut request = Request::new(data);
 
@@ -208,6 +243,10 @@ impl CloudConnection {
         request
             .metadata_mut()
             .insert("authorization", self.token.parse().unwrap());
+        // Lets the controller keep the behaviour this version expects
+        request
+            .metadata_mut()
+            .insert("protocol-version", VERSION.protocol.into());
 
         request
     }
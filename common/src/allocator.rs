@@ -90,16 +90,33 @@ where
             self.available.remove(&number);
             self.active.insert(number);
             Some(number)
-        } else if self.next < self.range.end {
-            let number = self.next;
-            self.next += T::from(1);
-            self.active.insert(number);
-            Some(number)
         } else {
+            while self.next < self.range.end {
+                let number = self.next;
+                self.next += T::from(1);
+                // Skip numbers that were claimed or are still active from before a resize
+                if self.active.insert(number) {
+                    return Some(number);
+                }
+            }
             None
         }
     }
 
+    /// Replaces the range while keeping all active numbers.
+    ///
+    /// Allocation restarts at the beginning of the new range and skips numbers that are still active.
+    /// Active numbers outside of the new range are not reused once they are released.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The new range.
+    pub fn resize(&mut self, range: Range<T>) {
+        self.next = range.start;
+        self.range = range;
+        self.available.clear();
+    }
+
     /// Releases a previously allocated number back to the allocator.
     ///
     /// If the number was active, it is removed from the active set and added to the available pool.
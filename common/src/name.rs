@@ -22,6 +22,15 @@ impl TimedName {
         }
     }
 
+    // Used for names that were generated by an earlier run
+    #[must_use]
+    pub fn from_existing(raw_name: &str, name: String) -> Self {
+        Self {
+            raw_name: raw_name.to_string(),
+            name,
+        }
+    }
+
     fn generate(cloud_identifier: Option<String>, name: &str, permanent: bool) -> String {
         let timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -8,11 +8,15 @@ use std::{
 
 use anyhow::Result;
 use auth::manager::AuthManager;
+use channel::manager::ChannelManager;
 use getset::{Getters, MutGetters};
 use group::manager::GroupManager;
 use node::manager::NodeManager;
 use plugin::manager::PluginManager;
-use server::{manager::ServerManager, screen::manager::ScreenManager};
+use server::{
+    manager::{RestartRequest, ServerManager},
+    screen::manager::ScreenManager,
+};
 use simplelog::{error, info};
 use subscriber::manager::SubscriberManager;
 use tls::TlsSetting;
@@ -26,6 +30,7 @@ use user::manager::UserManager;
 use crate::{config::Config, network::NetworkStack, task::manager::TaskManager};
 
 pub mod auth;
+pub mod channel;
 pub mod group;
 pub mod node;
 pub mod plugin;
@@ -63,6 +68,7 @@ pub struct Controller {
 pub struct Shared {
     pub auth: AuthManager,
     pub subscribers: SubscriberManager,
+    pub channels: ChannelManager,
     pub screens: ScreenManager,
     pub tls: TlsSetting,
 }
@@ -72,7 +78,8 @@ impl Controller {
         let shared = Arc::new(Shared {
             auth: AuthManager::init().await?,
             subscribers: SubscriberManager::init(),
-            screens: ScreenManager::init(),
+            channels: ChannelManager::init(&config),
+            screens: ScreenManager::init(&config),
             tls: TlsSetting::init(&config).await?,
         });
 
@@ -83,7 +90,7 @@ impl Controller {
         let groups = GroupManager::init(&nodes).await?;
 
         let servers = ServerManager::init();
-        let users = UserManager::init();
+        let users = UserManager::init().await?;
 
         Ok(Self {
             state: State::new(),
@@ -137,13 +144,15 @@ impl Controller {
         let start = Instant::now();
 
         // Tick plugin manager
-        self.plugins.tick().await?;
+        self.plugins.tick(&mut self.nodes, &self.servers).await?;
 
         // Tick node manager
         self.nodes.tick()?;
 
         // Tick group manager
-        self.groups.tick(&self.config, &mut self.servers)?;
+        self.groups
+            .tick(&self.config, &mut self.servers, &self.shared)
+            .await?;
 
         // Tick server manager
         self.servers
@@ -157,13 +166,24 @@ impl Controller {
             .await?;
 
         // Tick user manager
-        self.users.tick(&self.config)?;
+        self.users.tick(&self.config, &mut self.servers)?;
+
+        // Move waiting users onto free servers
+        self.groups
+            .tick_queues(&mut self.servers, &mut self.users, &self.shared)
+            .await?;
 
         // Tick subscriber manager
         self.shared.subscribers.tick().await?;
 
-        // Tick screen manager
-        self.shared.screens.tick().await?;
+        // Tick channel manager
+        self.shared.channels.tick().await?;
+
+        // Tick screen manager and restart the servers an alert asked for
+        for server in self.shared.screens.tick(&self.shared.subscribers).await? {
+            self.servers
+                .schedule_restart(RestartRequest::new(None, server));
+        }
 
         // Check if tick took longer than expected
         let elapsed = start.elapsed();
@@ -190,7 +210,7 @@ impl Controller {
         info!("Starting cleanup sequence...");
 
         // Cleanup user manager
-        self.users.cleanup()?;
+        self.users.cleanup().await?;
 
         // Cleanup server manager
         self.servers.cleanup()?;
@@ -204,6 +224,9 @@ impl Controller {
         // Cleanup subscription manager
         self.shared.subscribers.cleanup().await?;
 
+        // Cleanup channel manager
+        self.shared.channels.cleanup().await?;
+
         // Cleanup screen manager
         self.shared.screens.cleanup().await?;
 
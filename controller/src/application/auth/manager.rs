@@ -7,8 +7,9 @@ use tokio::{fs, sync::RwLock};
 use uuid::Uuid;
 
 use crate::{
-    application::auth::{
-        DEFAULT_ADMIN_PERMISSIONS, DEFAULT_ADMIN_USERNAME, permissions::Permissions,
+    application::{
+        auth::{DEFAULT_ADMIN_PERMISSIONS, DEFAULT_ADMIN_USERNAME, permissions::Permissions},
+        channel::ChannelAccess,
     },
     storage::{SaveToTomlFile, Storage},
 };
@@ -79,7 +80,7 @@ impl AuthManager {
         self.tokens.write().await.remove(token);
     }
 
-    pub async fn register_server(&self, uuid: Uuid) -> String {
+    pub async fn register_server(&self, uuid: Uuid, channels: ChannelAccess) -> String {
         let token = format!(
             "sctl_{}{}",
             Uuid::new_v4().as_simple(),
@@ -89,7 +90,7 @@ impl AuthManager {
         self.tokens
             .write()
             .await
-            .insert(token.clone(), AuthServer::create(uuid));
+            .insert(token.clone(), AuthServer::create(uuid, channels));
 
         token
     }
@@ -33,6 +33,17 @@ bitflags! {
 
         const LIST = 1 << 17;
 
-        const ALL = Self::REQUEST_STOP.bits() | Self::SET_RESOURCE.bits() | Self::DELETE_RESOURCE.bits() | Self::CREATE_NODE.bits() | Self::UPDATE_NODE.bits() | Self::GET_NODE.bits() | Self::CREATE_GROUP.bits() | Self::UPDATE_GROUP.bits() | Self::GET_GROUP.bits() | Self::SCHEDULE_SERVER.bits() | Self::GET_SERVER.bits() | Self::WRITE_TO_SCREEN.bits() | Self::READ_SCREEN.bits() | Self::GET_USER.bits() | Self::TRANSFER_USER.bits() | Self::READ_POWER_EVENTS.bits() | Self::READ_READY_EVENTS.bits() | Self::LIST.bits();
+        const RESTART_SERVER = 1 << 18;
+        const STOP_SERVER = 1 << 19;
+
+        const PUBLISH_CHANNEL = 1 << 20;
+        const TAP_CHANNEL = 1 << 21;
+
+        const KICK_USER = 1 << 22;
+        const BROADCAST = 1 << 23;
+
+        const MANAGE_PLUGINS = 1 << 24;
+
+        const ALL = Self::REQUEST_STOP.bits() | Self::SET_RESOURCE.bits() | Self::DELETE_RESOURCE.bits() | Self::CREATE_NODE.bits() | Self::UPDATE_NODE.bits() | Self::GET_NODE.bits() | Self::CREATE_GROUP.bits() | Self::UPDATE_GROUP.bits() | Self::GET_GROUP.bits() | Self::SCHEDULE_SERVER.bits() | Self::GET_SERVER.bits() | Self::WRITE_TO_SCREEN.bits() | Self::READ_SCREEN.bits() | Self::GET_USER.bits() | Self::TRANSFER_USER.bits() | Self::READ_POWER_EVENTS.bits() | Self::READ_READY_EVENTS.bits() | Self::LIST.bits() | Self::RESTART_SERVER.bits() | Self::STOP_SERVER.bits() | Self::PUBLISH_CHANNEL.bits() | Self::TAP_CHANNEL.bits() | Self::KICK_USER.bits() | Self::BROADCAST.bits() | Self::MANAGE_PLUGINS.bits();
     }
 }
@@ -1,6 +1,8 @@
 use getset::Getters;
 use uuid::Uuid;
 
+use crate::application::channel::ChannelAccess;
+
 use super::{
     AuthType, GenericAuthorization, OwnedAuthorization, permissions::Permissions, user::AdminUser,
 };
@@ -9,6 +11,8 @@ use super::{
 pub struct AuthServer {
     #[getset(get = "pub")]
     uuid: Uuid,
+    #[getset(get = "pub")]
+    channels: ChannelAccess,
 }
 
 impl GenericAuthorization for AuthServer {
@@ -28,12 +32,12 @@ impl GenericAuthorization for AuthServer {
     }
 
     fn recreate(&self) -> OwnedAuthorization {
-        AuthServer::create(self.uuid)
+        AuthServer::create(self.uuid, self.channels.clone())
     }
 }
 
 impl AuthServer {
-    pub fn create(uuid: Uuid) -> OwnedAuthorization {
-        Box::new(Self { uuid })
+    pub fn create(uuid: Uuid, channels: ChannelAccess) -> OwnedAuthorization {
+        Box::new(Self { uuid, channels })
     }
 }
@@ -0,0 +1,145 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
+
+use getset::Getters;
+use pattern::matches;
+use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+
+use crate::network::client::ChannelMsg;
+
+pub mod manager;
+pub mod pattern;
+
+// Declared in the group specification. An empty list allows every channel
+#[derive(Serialize, Deserialize, Clone, Default, Getters)]
+pub struct ChannelAccess {
+    #[getset(get = "pub")]
+    publish: Vec<String>,
+    #[getset(get = "pub")]
+    subscribe: Vec<String>,
+}
+
+pub enum StartPoint {
+    Live,
+    Sequence(u64),
+    Timestamp(u64),
+}
+
+pub struct Channel {
+    next_sequence: u64,
+    history: VecDeque<(Instant, ChannelMsg)>,
+    consumers: HashMap<String, Consumer>,
+}
+
+struct Consumer {
+    acknowledged: u64,
+    last_seen: Instant,
+}
+
+impl ChannelAccess {
+    pub fn new(publish: Vec<String>, subscribe: Vec<String>) -> Self {
+        Self { publish, subscribe }
+    }
+
+    pub fn can_publish(&self, channel: &str) -> bool {
+        self.publish.is_empty() || self.publish.iter().any(|pattern| matches(pattern, channel))
+    }
+
+    pub fn can_subscribe(&self, channel: &str) -> bool {
+        self.subscribe.is_empty()
+            || self
+                .subscribe
+                .iter()
+                .any(|pattern| matches(pattern, channel))
+    }
+}
+
+impl Channel {
+    fn new() -> Self {
+        Self {
+            next_sequence: 1,
+            history: VecDeque::new(),
+            consumers: HashMap::new(),
+        }
+    }
+
+    fn push(&mut self, mut message: ChannelMsg, limit: usize) -> ChannelMsg {
+        message.sequence = self.next_sequence;
+        self.next_sequence += 1;
+
+        self.history.push_back((Instant::now(), message.clone()));
+        while self.history.len() > limit {
+            self.history.pop_front();
+        }
+        message
+    }
+
+    fn replay(&mut self, start: &StartPoint, consumer: Option<&str>) -> Vec<ChannelMsg> {
+        let mut sequence = match start {
+            StartPoint::Live => None,
+            StartPoint::Sequence(sequence) => Some(*sequence),
+            StartPoint::Timestamp(timestamp) => Some(
+                self.history
+                    .iter()
+                    .find(|(_, message)| message.timestamp >= *timestamp)
+                    .map_or(self.next_sequence, |(_, message)| message.sequence),
+            ),
+        };
+
+        if let Some(name) = consumer {
+            let consumer = self
+                .consumers
+                .entry(name.to_string())
+                .or_insert_with(|| Consumer {
+                    acknowledged: sequence.unwrap_or(self.next_sequence).saturating_sub(1),
+                    last_seen: Instant::now(),
+                });
+            consumer.last_seen = Instant::now();
+            // Redeliver everything the consumer has not acknowledged yet
+            sequence.get_or_insert(consumer.acknowledged + 1);
+        }
+
+        let Some(sequence) = sequence else {
+            return vec![];
+        };
+        self.history
+            .iter()
+            .filter(|(_, message)| message.sequence >= sequence)
+            .map(|(_, message)| message.clone())
+            .collect()
+    }
+
+    fn history_since(&self, timestamp: u64) -> impl Iterator<Item = &ChannelMsg> {
+        self.history
+            .iter()
+            .map(|(_, message)| message)
+            .filter(move |message| message.timestamp >= timestamp)
+    }
+
+    fn acknowledge(&mut self, consumer: &str, sequence: u64) -> bool {
+        let Some(consumer) = self.consumers.get_mut(consumer) else {
+            return false;
+        };
+        consumer.acknowledged = consumer.acknowledged.max(sequence);
+        consumer.last_seen = Instant::now();
+        true
+    }
+
+    // Returns true if the channel holds no state anymore
+    fn prune(&mut self, retention: &Duration, consumer_retention: &Duration) -> bool {
+        while self
+            .history
+            .front()
+            .is_some_and(|(timestamp, _)| timestamp.elapsed() >= *retention)
+        {
+            self.history.pop_front();
+        }
+        // Consumers keep their position even if they missed every message that is still in the history
+        self.consumers
+            .retain(|_, consumer| consumer.last_seen.elapsed() < *consumer_retention);
+        self.history.is_empty() && self.consumers.is_empty()
+    }
+}
@@ -0,0 +1,217 @@
+use std::{collections::HashMap, str::FromStr, time::Duration};
+
+use anyhow::Result;
+use tokio::{
+    spawn,
+    sync::{RwLock, oneshot},
+    time::timeout,
+};
+use tokio_stream::{StreamExt, wrappers::ReceiverStream};
+use tonic::Status;
+use uuid::Uuid;
+
+use crate::{
+    application::subscriber::{Subscriber, watcher::Watcher},
+    config::Config,
+    network::client::{ChannelMsg, ChannelReplyMsg, ChannelRequestMsg},
+};
+
+use super::{
+    Channel, StartPoint,
+    pattern::{check_name, is_pattern, matches},
+};
+
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub struct ChannelManager {
+    channels: RwLock<HashMap<String, Channel>>,
+    pending: RwLock<HashMap<Uuid, oneshot::Sender<ChannelReplyMsg>>>,
+
+    /* Retention */
+    history: usize,
+    retention: Duration,
+    consumer_retention: Duration,
+}
+
+impl ChannelManager {
+    pub fn init(config: &Config) -> Self {
+        Self {
+            channels: RwLock::new(HashMap::new()),
+            pending: RwLock::new(HashMap::new()),
+            history: config.channel_history(),
+            retention: *config.channel_retention(),
+            consumer_retention: *config.consumer_retention(),
+        }
+    }
+
+    pub async fn publish(
+        &self,
+        watcher: &Watcher<String, ChannelMsg>,
+        message: ChannelMsg,
+    ) -> Result<u32, Status> {
+        check_name(&message.channel)?;
+        if is_pattern(&message.channel) {
+            return Err(Status::invalid_argument(
+                "Messages can not be published to a channel pattern",
+            ));
+        }
+
+        // The lock is held while publishing so that subscribers never miss or duplicate a message.
+        // This is only fine because publishing never waits for a subscriber
+        let mut channels = self.channels.write().await;
+        let message = channels
+            .entry(message.channel.clone())
+            .or_insert_with(Channel::new)
+            .push(message, self.history);
+
+        let channel = message.channel.clone();
+        Ok(watcher
+            .try_publish_to_matching(|pattern| matches(pattern, &channel), message)
+            .await)
+    }
+
+    pub async fn subscribe(
+        &self,
+        watcher: &Watcher<String, ChannelMsg>,
+        channel: String,
+        start: &StartPoint,
+        consumer: Option<&str>,
+    ) -> Result<ReceiverStream<Result<ChannelMsg, Status>>, Status> {
+        check_name(&channel)?;
+        let (subscriber, receiver) = Subscriber::create_network();
+
+        let mut channels = self.channels.write().await;
+        let replay = if is_pattern(&channel) {
+            if consumer.is_some() {
+                return Err(Status::invalid_argument(
+                    "Acknowledgements are not supported for channel patterns",
+                ));
+            }
+            match start {
+                StartPoint::Live => vec![],
+                StartPoint::Sequence(_) => {
+                    return Err(Status::invalid_argument(
+                        "Channel patterns can only be replayed from a timestamp",
+                    ));
+                }
+                StartPoint::Timestamp(timestamp) => {
+                    let mut replay = channels
+                        .iter()
+                        .filter(|(name, _)| matches(&channel, name))
+                        .flat_map(|(_, channel)| channel.history_since(*timestamp).cloned())
+                        .collect::<Vec<_>>();
+                    replay.sort_by_key(|message| message.timestamp);
+                    replay
+                }
+            }
+        } else {
+            channels
+                .entry(channel.clone())
+                .or_insert_with(Channel::new)
+                .replay(start, consumer)
+        };
+        if replay.is_empty() {
+            watcher.subscribe_to_scope(channel, subscriber).await;
+            return Ok(receiver);
+        }
+
+        // The history can be larger than the buffer of the subscriber so we have to replay it in the background
+        let (live, mut stream) = Subscriber::create_network();
+        watcher.subscribe_to_scope(channel, live).await;
+        drop(channels);
+
+        spawn(async move {
+            for message in replay {
+                if !subscriber.send_message(message).await {
+                    return;
+                }
+            }
+            while let Some(message) = stream.next().await {
+                if !subscriber.send_network(message).await {
+                    return;
+                }
+            }
+        });
+        Ok(receiver)
+    }
+
+    pub async fn acknowledge(&self, channel: &str, consumer: &str, sequence: u64) -> bool {
+        self.channels
+            .write()
+            .await
+            .get_mut(channel)
+            .is_some_and(|channel| channel.acknowledge(consumer, sequence))
+    }
+
+    pub async fn request(
+        &self,
+        watcher: &Watcher<String, ChannelRequestMsg>,
+        mut request: ChannelRequestMsg,
+    ) -> Result<ChannelReplyMsg, Status> {
+        check_name(&request.channel)?;
+        if is_pattern(&request.channel) {
+            return Err(Status::invalid_argument(
+                "Requests can not be sent to a channel pattern",
+            ));
+        }
+
+        let id = Uuid::new_v4();
+        let duration = match request.timeout {
+            0 => DEFAULT_REQUEST_TIMEOUT,
+            millis => Duration::from_millis(u64::from(millis)).min(MAX_REQUEST_TIMEOUT),
+        };
+        request.id = id.to_string();
+
+        let (sender, receiver) = oneshot::channel();
+        self.pending.write().await.insert(id, sender);
+
+        let channel = request.channel.clone();
+        if watcher
+            .try_publish_to_matching(|pattern| matches(pattern, &channel), request)
+            .await
+            == 0
+        {
+            self.pending.write().await.remove(&id);
+            return Err(Status::unavailable(
+                "No one is listening for requests on this channel",
+            ));
+        }
+
+        let result = timeout(duration, receiver).await;
+        self.pending.write().await.remove(&id);
+        match result {
+            Ok(Ok(reply)) => Ok(reply),
+            _ => Err(Status::deadline_exceeded("No reply received in time")),
+        }
+    }
+
+    // Only the first reply is delivered to the requester
+    pub async fn reply(&self, reply: ChannelReplyMsg) -> bool {
+        let Ok(id) = Uuid::from_str(&reply.id) else {
+            return false;
+        };
+        self.pending
+            .write()
+            .await
+            .remove(&id)
+            .is_some_and(|sender| sender.send(reply).is_ok())
+    }
+}
+
+// Ticking
+impl ChannelManager {
+    pub async fn tick(&self) -> Result<()> {
+        self.channels
+            .write()
+            .await
+            .retain(|_, channel| !channel.prune(&self.retention, &self.consumer_retention));
+        Ok(())
+    }
+
+    pub async fn cleanup(&self) -> Result<()> {
+        self.channels.write().await.clear();
+        self.pending.write().await.clear();
+        Ok(())
+    }
+}
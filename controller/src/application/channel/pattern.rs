@@ -0,0 +1,50 @@
+use tonic::Status;
+
+// Limits that keep matching cheap. Matching takes time proportional to the product of both segment counts
+const MAX_NAME_LENGTH: usize = 256;
+const MAX_SEGMENTS: usize = 16;
+
+// Channels are split into segments by dots. `*` matches exactly one segment and `#` matches zero or more.
+// The name can be a pattern itself. In that case this checks if the pattern covers every channel the name could match
+pub fn matches(pattern: &str, name: &str) -> bool {
+    let mut pattern = pattern.split('.').collect::<Vec<_>>();
+    // Consecutive `#` match the same as a single one
+    pattern.dedup_by(|segment, previous| *segment == "#" && *previous == "#");
+    let name = name.split('.').collect::<Vec<_>>();
+
+    // Each row holds for every suffix of the name if it is matched by the current suffix of the pattern
+    let mut next = vec![false; name.len() + 1];
+    next[name.len()] = true;
+    for expected in pattern.iter().rev() {
+        let mut current = vec![false; name.len() + 1];
+        for index in (0..=name.len()).rev() {
+            current[index] = match (*expected, name.get(index)) {
+                ("#", segment) => next[index] || (segment.is_some() && current[index + 1]),
+                ("*", Some(segment)) => *segment != "#" && next[index + 1],
+                (expected, Some(segment)) => expected == *segment && next[index + 1],
+                _ => false,
+            };
+        }
+        next = current;
+    }
+    next[0]
+}
+
+pub fn is_pattern(name: &str) -> bool {
+    name.split('.')
+        .any(|segment| segment == "*" || segment == "#")
+}
+
+pub fn check_name(name: &str) -> Result<(), Status> {
+    if name.len() > MAX_NAME_LENGTH {
+        return Err(Status::invalid_argument(format!(
+            "Channel names can be at most {MAX_NAME_LENGTH} bytes long"
+        )));
+    }
+    if name.split('.').count() > MAX_SEGMENTS {
+        return Err(Status::invalid_argument(format!(
+            "Channel names can have at most {MAX_SEGMENTS} segments"
+        )));
+    }
+    Ok(())
+}
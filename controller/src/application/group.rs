@@ -1,15 +1,17 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
+use alert::AlertRule;
 use anyhow::{Result, anyhow};
 use common::allocator::NumberAllocator;
 use getset::{Getters, Setters};
 use manager::stored::StoredGroup;
+use queue::TransferQueue;
 use serde::{Deserialize, Serialize};
 use simplelog::{debug, info};
 use tokio::fs;
 
 use crate::{
-    application::server::manager::StopRequest,
+    application::{Shared, server::manager::StopRequest},
     config::Config,
     resource::DeleteResourceError,
     storage::{SaveToTomlFile, Storage},
@@ -23,7 +25,9 @@ use super::{
     },
 };
 
+pub mod alert;
 pub mod manager;
+pub mod queue;
 
 #[derive(Getters, Setters)]
 pub struct Group {
@@ -47,9 +51,16 @@ pub struct Group {
     #[getset(get = "pub", set = "pub")]
     specification: Specification,
 
+    /* Console */
+    #[getset(get = "pub")]
+    alerts: Vec<AlertRule>,
+
     /* What do i need to know? */
     id_allocator: NumberAllocator<usize>,
     servers: HashMap<NameAndUuid, GroupServer>,
+    queue: TransferQueue,
+    // The amount of servers the group wanted during the last tick
+    target: u32,
 }
 
 impl Group {
@@ -58,10 +69,11 @@ impl Group {
         clippy::cast_possible_truncation,
         reason = "I have to clean this up"
     )]
-    pub fn tick(&mut self, config: &Config, servers: &mut ServerManager) -> Result<()> {
+    // Returns the new target count of servers if it changed
+    pub fn tick(&mut self, config: &Config, servers: &mut ServerManager) -> Result<Option<u32>> {
         if self.status == LifecycleStatus::Inactive {
             // Do not tick this group because it is inactive
-            return Ok(());
+            return Ok(None);
         }
 
         let mut target_count = self.constraints.minimum;
@@ -70,7 +82,9 @@ impl Group {
         if self.scaling.enabled {
             self.servers.retain(|id, server| match &server.1 {
                 Stage::Active => servers.get_server(id.uuid()).is_some_and(|server| {
-                    if *server.connected_users() as f32 / *self.specification.max_players() as f32
+                    // Users that are on their way count as load
+                    if (server.connected_users() + server.reserved()) as f32
+                        / *self.specification.max_players() as f32
                         >= self.scaling.start_threshold
                     {
                         target_count += 1;
@@ -80,6 +94,13 @@ impl Group {
                 Stage::Queueing | Stage::Stopping => true,
             });
 
+            if self.queue_needs_server() {
+                target_count += 1;
+            }
+
+            // Neither load nor the queue may grow the group past its maximum
+            target_count = target_count.min(self.constraints.maximum.max(self.constraints.minimum));
+
             // Count all servers that are active
             let current_count = self
                 .servers
@@ -93,7 +114,7 @@ impl Group {
                 self.servers.retain(|id, group_server| {
                     match &group_server.1 {
                         Stage::Active => servers.get_server_mut(id.uuid()).is_some_and(|server| {
-                            if server.connected_users() == &0 {
+                            if server.connected_users() == &0 && server.reserved() == &0 {
                                 if server.flags().should_stop() && to_stop > 0 {
                                     debug!(
                                     "Server {} is empty and reached the timeout, stopping it...",
@@ -156,7 +177,11 @@ impl Group {
             servers.schedule_start(request);
         }
 
-        Ok(())
+        if self.target == target_count {
+            return Ok(None);
+        }
+        self.target = target_count;
+        Ok(Some(target_count))
     }
 
     pub async fn delete(&mut self) -> Result<(), DeleteResourceError> {
@@ -176,13 +201,24 @@ impl Group {
         Ok(())
     }
 
-    pub async fn set_active(&mut self, active: bool, servers: &mut ServerManager) -> Result<()> {
+    pub async fn set_active(
+        &mut self,
+        active: bool,
+        servers: &mut ServerManager,
+        shared: &Arc<Shared>,
+    ) -> Result<()> {
         if active && self.status == LifecycleStatus::Inactive {
             // Activate group
 
             self.status = LifecycleStatus::Active;
             self.save().await?;
             info!("Group {} is now active", self.name);
+            shared
+                .subscribers
+                .plugin()
+                .group_change_active()
+                .publish((self.name.clone(), true))
+                .await;
         } else if !active && self.status == LifecycleStatus::Active {
             // Retire group
             // Stop all servers and cancel all starts
@@ -199,20 +235,45 @@ impl Group {
             });
 
             self.status = LifecycleStatus::Inactive;
+            self.target = 0;
             self.save().await?;
             info!("Group {} is now inactive", self.name);
+            shared
+                .subscribers
+                .plugin()
+                .group_change_active()
+                .publish((self.name.clone(), false))
+                .await;
         }
 
         Ok(())
     }
 
-    pub fn find_free_server<'a>(&self, servers: &'a ServerManager) -> Option<&'a Server> {
+    // Users are waiting for a slot and nothing is starting yet. This is only checked when scaling is enabled.
+    // Without scaling the group keeps its minimum and queued users wait for slots to free up, as extra servers
+    // would never be stopped again
+    fn queue_needs_server(&self) -> bool {
+        !self.queue.is_empty()
+            && !self
+                .servers
+                .values()
+                .any(|server| matches!(server.1, Stage::Queueing))
+    }
+
+    // Picks the least loaded server that still has enough free slots
+    pub fn find_free_server<'a>(
+        &self,
+        servers: &'a ServerManager,
+        slots: u32,
+    ) -> Option<&'a Server> {
         self.servers
             .iter()
-            .find_map(|(id, server)| match &server.1 {
+            .filter_map(|(id, server)| match &server.1 {
                 Stage::Active => servers.get_server(id.uuid()),
                 Stage::Queueing | Stage::Stopping => None,
             })
+            .filter(|server| server.has_free_slots(slots))
+            .min_by_key(|server| server.connected_users() + server.reserved())
     }
 
     pub fn set_server_active(&mut self, id: &NameAndUuid) {
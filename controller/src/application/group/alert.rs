@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+use getset::Getters;
+use serde::{Deserialize, Serialize};
+
+// A pattern that is matched against every line the servers of a group print
+#[derive(Serialize, Deserialize, Clone, Getters)]
+pub struct AlertRule {
+    #[getset(get = "pub")]
+    name: String,
+    #[getset(get = "pub")]
+    pattern: String,
+    #[serde(default)]
+    #[getset(get = "pub")]
+    action: AlertAction,
+    // Matches inside this window are counted but do not trigger the action again
+    #[serde(default = "default_cooldown")]
+    #[getset(get = "pub")]
+    cooldown: Duration,
+    // Restarts of the group that this rule may trigger inside the window before it only notifies
+    #[serde(default = "default_max_restarts")]
+    #[getset(get = "pub")]
+    max_restarts: u32,
+    #[serde(default = "default_restart_window")]
+    #[getset(get = "pub")]
+    restart_window: Duration,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq)]
+pub enum AlertAction {
+    #[serde(rename = "notify")]
+    #[default]
+    Notify,
+    #[serde(rename = "restart")]
+    Restart,
+}
+
+const fn default_cooldown() -> Duration {
+    Duration::from_secs(60)
+}
+
+const fn default_max_restarts() -> u32 {
+    3
+}
+
+const fn default_restart_window() -> Duration {
+    Duration::from_hours(1)
+}
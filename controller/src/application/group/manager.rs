@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 use anyhow::Result;
 use common::allocator::NumberAllocator;
@@ -8,16 +8,17 @@ use tokio::fs;
 
 use crate::{
     application::{
-        OptVoter, Voter,
+        OptVoter, Shared, Voter,
         node::manager::NodeManager,
         server::{Resources, Specification, manager::ServerManager},
+        user::manager::UserManager,
     },
     config::Config,
     resource::{CreateResourceError, DeleteResourceError, UpdateResourceError},
     storage::Storage,
 };
 
-use super::{Group, ScalingPolicy, StartConstraints};
+use super::{Group, ScalingPolicy, StartConstraints, queue::TransferQueue};
 
 pub struct GroupManager {
     voter: OptVoter,
@@ -171,22 +172,54 @@ impl Group {
             scaling: group.scaling().clone(),
             resources: group.resources().clone(),
             specification: group.specification().clone(),
+            alerts: group.alerts().clone(),
             id_allocator: NumberAllocator::new(1..usize::MAX),
             servers: HashMap::new(),
+            queue: TransferQueue::default(),
+            target: 0,
         }
     }
 }
 
 // Ticking
 impl GroupManager {
-    pub fn tick(&mut self, config: &Config, servers: &mut ServerManager) -> Result<()> {
+    pub async fn tick(
+        &mut self,
+        config: &Config,
+        servers: &mut ServerManager,
+        shared: &Arc<Shared>,
+    ) -> Result<()> {
         if self.voter.is_some() {
             // Do not tick if we are shutting down
             return Ok(());
         }
 
         for group in self.groups.values_mut() {
-            group.tick(config, servers)?;
+            if let Some(target) = group.tick(config, servers)? {
+                shared
+                    .subscribers
+                    .plugin()
+                    .group_scaled()
+                    .publish((group.name.clone(), target))
+                    .await;
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn tick_queues(
+        &mut self,
+        servers: &mut ServerManager,
+        users: &mut UserManager,
+        shared: &Arc<Shared>,
+    ) -> Result<()> {
+        if self.voter.is_some() {
+            // Do not move users if we are shutting down
+            return Ok(());
+        }
+
+        for group in self.groups.values_mut() {
+            group.tick_queue(servers, users, shared).await;
         }
         Ok(())
     }
@@ -210,7 +243,7 @@ pub(super) mod stored {
 
     use crate::{
         application::{
-            group::{Group, ScalingPolicy, StartConstraints},
+            group::{Group, ScalingPolicy, StartConstraints, alert::AlertRule},
             node::LifecycleStatus,
             server::{Resources, Specification},
         },
@@ -236,6 +269,11 @@ pub(super) mod stored {
         resources: Resources,
         #[getset(get = "pub", get_mut = "pub")]
         specification: Specification,
+
+        /* Console */
+        #[serde(default)]
+        #[getset(get = "pub", get_mut = "pub")]
+        alerts: Vec<AlertRule>,
     }
 
     impl StoredGroup {
@@ -253,6 +291,7 @@ pub(super) mod stored {
                 scaling,
                 resources,
                 specification,
+                alerts: vec![],
             }
         }
 
@@ -264,6 +303,7 @@ pub(super) mod stored {
                 scaling: group.scaling.clone(),
                 resources: group.resources.clone(),
                 specification: group.specification.clone(),
+                alerts: group.alerts.clone(),
             }
         }
     }
@@ -0,0 +1,187 @@
+use std::{collections::VecDeque, mem, sync::Arc};
+
+use simplelog::{debug, warn};
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        Shared,
+        node::LifecycleStatus,
+        server::manager::ServerManager,
+        user::{
+            CurrentServer,
+            manager::UserManager,
+            transfer::{Transfer, TransferReporter, TransferTarget},
+        },
+    },
+    network::client::{QueueMsg, TransferState},
+};
+
+use super::Group;
+
+// Users waiting for a slot on one of the servers of a group
+#[derive(Default)]
+pub struct TransferQueue(VecDeque<QueuedUser>);
+
+struct QueuedUser {
+    id: Uuid,
+    user: Uuid,
+    from: Uuid,
+    reporter: TransferReporter,
+}
+
+impl TransferQueue {
+    // Returns the 1-based position of the user and the reporter of an older request for the same user
+    pub fn enqueue(
+        &mut self,
+        user: Uuid,
+        from: Uuid,
+        reporter: TransferReporter,
+    ) -> (usize, Option<TransferReporter>) {
+        if let Some(position) = self.0.iter().position(|entry| entry.user == user) {
+            // The user might have been moved to another server in the meantime
+            let entry = &mut self.0[position];
+            entry.from = from;
+            let previous = mem::replace(&mut entry.reporter, reporter);
+            return (position + 1, Some(previous));
+        }
+        self.0.push_back(QueuedUser {
+            id: Uuid::new_v4(),
+            user,
+            from,
+            reporter,
+        });
+        (self.0.len(), None)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Group {
+    pub fn accepts_queued_users(&self, target: &TransferTarget) -> bool {
+        self.status == LifecycleStatus::Active
+            && matches!(target, TransferTarget::Group(group) if *group == self.name)
+    }
+
+    pub async fn enqueue_user(
+        &mut self,
+        user: Uuid,
+        from: Uuid,
+        reporter: TransferReporter,
+        shared: &Arc<Shared>,
+    ) {
+        let (position, previous) = self.queue.enqueue(user, from, reporter);
+        debug!(
+            "User {} is waiting for a free server in group {} at position {}",
+            user, self.name, position
+        );
+        let entry = &self.queue.0[position - 1];
+        if let Some(previous) = previous {
+            previous.report(
+                &entry.id,
+                &user,
+                None,
+                TransferState::Rejected,
+                Some("Replaced by a newer transfer request".to_string()),
+            );
+        }
+        entry.reporter.report(
+            &entry.id,
+            &user,
+            None,
+            TransferState::Pending,
+            Some(format!("Waiting for a free server at position {position}")),
+        );
+        shared
+            .subscribers
+            .network()
+            .queue()
+            .publish_to_scope(&from, self.new_queue_update(&user, position))
+            .await;
+    }
+
+    pub async fn tick_queue(
+        &mut self,
+        servers: &mut ServerManager,
+        users: &mut UserManager,
+        shared: &Arc<Shared>,
+    ) {
+        if self.queue.is_empty() {
+            return;
+        }
+
+        // Drop users that left the server they were waiting on
+        let mut dropped = vec![];
+        let active = self.status == LifecycleStatus::Active;
+        for entry in mem::take(&mut self.queue.0) {
+            if active
+                && users.get_user(&entry.user).is_some_and(|user| {
+                    matches!(user.server(), CurrentServer::Connected(server) if *server.uuid() == entry.from)
+                })
+            {
+                self.queue.0.push_back(entry);
+            } else {
+                dropped.push(entry);
+            }
+        }
+        let mut changed = !dropped.is_empty();
+        for entry in dropped {
+            entry.reporter.report(
+                &entry.id,
+                &entry.user,
+                None,
+                TransferState::Rejected,
+                Some("User is no longer waiting for this group".to_string()),
+            );
+        }
+
+        while let Some(to) = self.find_free_server(servers, 1) {
+            let Some(entry) = self.queue.0.pop_front() else {
+                break;
+            };
+            changed = true;
+
+            let Some(user) = users.get_user_mut(&entry.user) else {
+                continue;
+            };
+            let CurrentServer::Connected(from) = user.server() else {
+                continue;
+            };
+            let from = from.clone();
+            let target = *to.id().uuid();
+            let mut transfer = Transfer::new(entry.id, user, from, to, entry.reporter);
+            if let Err(error) = Transfer::transfer_user(&mut transfer, shared).await {
+                warn!(
+                    "Failed to transfer queued user {} to group {}: {}",
+                    entry.user,
+                    self.name,
+                    error.message()
+                );
+            } else if let Some(server) = servers.get_server_mut(&target) {
+                // Hold the slot until the user arrives
+                server.reserve_slots(1);
+            }
+        }
+
+        if changed {
+            for (index, entry) in self.queue.0.iter().enumerate() {
+                shared
+                    .subscribers
+                    .network()
+                    .queue()
+                    .publish_to_scope(&entry.from, self.new_queue_update(&entry.user, index + 1))
+                    .await;
+            }
+        }
+    }
+
+    fn new_queue_update(&self, user: &Uuid, position: usize) -> QueueMsg {
+        QueueMsg {
+            id: user.to_string(),
+            group: self.name.clone(),
+            position: u32::try_from(position).unwrap_or(u32::MAX),
+        }
+    }
+}
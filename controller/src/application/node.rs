@@ -1,3 +1,5 @@
+use std::{mem::replace, sync::Arc};
+
 use anyhow::Result;
 use common::network::HostAndPort;
 use getset::{Getters, Setters};
@@ -14,6 +16,7 @@ use crate::{
 };
 
 use super::{
+    Shared,
     plugin::BoxedNode,
     server::{
         Resources, Server, Specification, guard::Guard, manager::StartRequest, screen::BoxedScreen,
@@ -70,19 +73,31 @@ impl Node {
         Ok(())
     }
 
-    pub async fn set_active(&mut self, active: bool) -> Result<()> {
+    pub async fn set_active(&mut self, active: bool, shared: &Arc<Shared>) -> Result<()> {
         if active && self.status == LifecycleStatus::Inactive {
             // Activate node
 
             self.status = LifecycleStatus::Active;
             self.save().await?;
             info!("Node {} is now active", self.name);
+            shared
+                .subscribers
+                .plugin()
+                .node_change_active()
+                .publish((self.name.clone(), true))
+                .await;
         } else if !active && self.status == LifecycleStatus::Active {
             // Retire node
 
             self.status = LifecycleStatus::Inactive;
             self.save().await?;
             info!("Node {} is now inactive", self.name);
+            shared
+                .subscribers
+                .plugin()
+                .node_change_active()
+                .publish((self.name.clone(), false))
+                .await;
         }
 
         Ok(())
@@ -110,6 +125,11 @@ impl Node {
             .await
     }
 
+    // Used after the plugin was reloaded. The caller drops the old instance together with its plugin
+    pub fn replace_instance(&mut self, instance: BoxedNode) -> BoxedNode {
+        replace(&mut self.instance, instance)
+    }
+
     pub async fn cleanup(&mut self) -> Result<()> {
         self.instance.cleanup().await
     }
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use simplelog::{debug, error, info, warn};
 use stored::StoredNode;
 use tokio::fs;
@@ -9,7 +9,7 @@ use url::Url;
 use crate::{
     application::{
         group::manager::GroupManager,
-        plugin::{BoxedNode, manager::PluginManager},
+        plugin::{BoxedNode, BoxedPlugin, manager::PluginManager},
         server::manager::ServerManager,
     },
     resource::{CreateResourceError, DeleteResourceError, UpdateResourceError},
@@ -18,6 +18,9 @@ use crate::{
 
 use super::{Capabilities, Node};
 
+// Node instances of a plugin that still have to be installed
+pub type PluginNodes = Vec<(String, StoredNode, BoxedNode)>;
+
 pub struct NodeManager {
     nodes: HashMap<String, Node>,
 }
@@ -64,6 +67,98 @@ impl NodeManager {
         Ok(Self { nodes })
     }
 
+    pub fn is_plugin_used(&self, plugin: &str) -> bool {
+        self.nodes.values().any(|node| node.plugin() == plugin)
+    }
+
+    // Settings of the loaded nodes of a plugin, used to initialize them again once the plugin was reloaded
+    pub fn plugin_node_settings(&self, plugin: &str) -> HashMap<String, (Capabilities, Url)> {
+        self.nodes
+            .values()
+            .filter(|node| node.plugin() == plugin)
+            .map(|node| {
+                (
+                    node.name().clone(),
+                    (node.capabilities().clone(), node.controller().clone()),
+                )
+            })
+            .collect()
+    }
+
+    // Initializes the nodes of a plugin that was loaded or reloaded while the controller is running
+    // Runs off the controller loop, the result is applied with install_plugin_nodes
+    pub async fn init_plugin_nodes(
+        plugin_name: &str,
+        plugin: &BoxedPlugin,
+        loaded: HashMap<String, (Capabilities, Url)>,
+    ) -> Result<PluginNodes> {
+        let mut instances = vec![];
+        let result = Self::init_stored_nodes(plugin_name, plugin, &loaded, &mut instances).await;
+        if result.is_err() {
+            for (_, _, instance) in &mut instances {
+                // Before we can drop the node we have to drop the wasm resources first
+                if let Err(error) = instance.cleanup().await {
+                    warn!("Failed to drop node instance: {}", error);
+                }
+            }
+        }
+        result.map(|()| instances)
+    }
+
+    async fn init_stored_nodes(
+        plugin_name: &str,
+        plugin: &BoxedPlugin,
+        loaded: &HashMap<String, (Capabilities, Url)>,
+        instances: &mut PluginNodes,
+    ) -> Result<()> {
+        for (_, _, name, value) in Storage::for_each_content_toml::<StoredNode>(
+            &Storage::nodes_directory(),
+            "Failed to read node from file",
+        )
+        .await?
+        {
+            if value.plugin() != plugin_name {
+                continue;
+            }
+
+            match loaded.get(&name) {
+                Some((capabilities, controller)) => {
+                    // The old instance keeps running the node, so the reload has to fail
+                    let instance = plugin
+                        .init_node(&name, capabilities, controller)
+                        .await
+                        .map_err(|error| anyhow!("Failed to initialize node {name}: {error}"))?;
+                    instances.push((name, value, instance));
+                }
+                // The node was skipped because its plugin was missing
+                None => match plugin
+                    .init_node(&name, value.capabilities(), value.controller())
+                    .await
+                {
+                    Ok(instance) => instances.push((name, value, instance)),
+                    Err(error) => error!("Failed to initialize node {}: {}", name, error),
+                },
+            }
+        }
+        Ok(())
+    }
+
+    // Returns the instances that were replaced
+    pub fn install_plugin_nodes(&mut self, instances: PluginNodes) -> Vec<BoxedNode> {
+        let mut replaced = vec![];
+        for (name, value, instance) in instances {
+            if let Some(node) = self.nodes.get_mut(&name) {
+                replaced.push(node.replace_instance(instance));
+                info!("Reinitialized node {}", name);
+            } else {
+                self.nodes
+                    .insert(name.clone(), Node::new(&name, &value, instance));
+                info!("Loaded node {}", name);
+            }
+        }
+        replaced
+    }
+
     pub async fn delete_node(
         &mut self,
         name: &str,
@@ -5,17 +5,23 @@ use tokio::task::JoinHandle;
 use tonic::async_trait;
 use url::Url;
 
+use config::ConfigSchema;
+
 use super::{
     node::Capabilities,
     server::{Server, guard::Guard, manager::StartRequest, screen::BoxedScreen},
 };
 
+pub mod config;
 pub mod manager;
 mod runtime;
 
 pub type BoxedPlugin = Box<dyn GenericPlugin + Send + Sync>;
 pub type BoxedNode = Box<dyn GenericNode + Send + Sync>;
 
+// The inner result is the answer of the plugin. Rejections carry the reason
+pub type ConfigCheckHandle = JoinHandle<Result<Result<(), String>>>;
+
 #[async_trait]
 pub trait GenericPlugin {
     async fn init(&self) -> Result<Information>;
@@ -32,6 +38,11 @@ pub trait GenericPlugin {
     /* Ticking */
     fn tick(&self) -> JoinHandle<Result<()>>;
 
+    /* Configuration */
+    fn config_schema(&self) -> Option<&ConfigSchema>;
+    fn validate_config(&self, content: &str) -> ConfigCheckHandle;
+    fn config_changed(&self, content: &str) -> ConfigCheckHandle;
+
     /* Management */
     async fn cleanup(&mut self) -> Result<()>;
 }
@@ -49,6 +60,8 @@ pub trait GenericNode {
     fn start(&self, server: &Server) -> JoinHandle<Result<BoxedScreen>>;
     fn restart(&self, server: &Server) -> JoinHandle<Result<()>>;
     fn stop(&self, server: &Server, guard: Guard) -> JoinHandle<Result<()>>;
+    // Takes over a server that the previous instance of the plugin started
+    fn adopt(&self, server: &Server) -> JoinHandle<Result<BoxedScreen>>;
 
     /* Memory */
     async fn cleanup(&mut self) -> Result<()>;
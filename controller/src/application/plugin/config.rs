@@ -0,0 +1,125 @@
+use std::path::{Component, Path, PathBuf};
+
+use getset::Getters;
+use toml::{Table, Value};
+
+use crate::storage::Storage;
+
+#[derive(Getters)]
+pub struct ConfigSchema {
+    #[getset(get = "pub")]
+    file: String,
+    #[getset(get = "pub")]
+    fields: Vec<ConfigField>,
+}
+
+#[derive(Getters)]
+pub struct ConfigField {
+    #[getset(get = "pub")]
+    path: String,
+    #[getset(get = "pub")]
+    kind: ConfigKind,
+    #[getset(get = "pub")]
+    description: String,
+    #[getset(get = "pub")]
+    required: bool,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ConfigKind {
+    String,
+    Integer,
+    Float,
+    Boolean,
+    Array,
+    Table,
+}
+
+impl ConfigSchema {
+    #[cfg(feature = "wasm-plugins")]
+    pub fn new(file: String, fields: Vec<ConfigField>) -> Self {
+        Self { file, fields }
+    }
+
+    // The file is declared by the plugin, so it must not leave the configs directory of the plugin
+    pub fn path(&self, plugin: &str) -> Option<PathBuf> {
+        let file = Path::new(&self.file);
+        if file.as_os_str().is_empty()
+            || !file
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return None;
+        }
+        Some(Storage::config_directory_for_plugin(plugin).join(file))
+    }
+
+    // Only checks what the schema describes. Everything else is up to the plugin
+    pub fn check(&self, content: &str) -> Result<(), String> {
+        let table = content
+            .parse::<Table>()
+            .map_err(|error| format!("Invalid TOML: {error}"))?;
+
+        for field in &self.fields {
+            match Self::lookup(&table, &field.path) {
+                Some(value) if ConfigKind::of(value) != field.kind => {
+                    return Err(format!(
+                        "Field {} must be of type {}",
+                        field.path,
+                        field.kind.name()
+                    ));
+                }
+                None if field.required => {
+                    return Err(format!("Missing required field {}", field.path));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn lookup<'a>(table: &'a Table, path: &str) -> Option<&'a Value> {
+        let mut keys = path.split('.');
+        let mut value = table.get(keys.next()?)?;
+        for key in keys {
+            value = value.as_table()?.get(key)?;
+        }
+        Some(value)
+    }
+}
+
+impl ConfigField {
+    #[cfg(feature = "wasm-plugins")]
+    pub fn new(path: String, kind: ConfigKind, description: String, required: bool) -> Self {
+        Self {
+            path,
+            kind,
+            description,
+            required,
+        }
+    }
+}
+
+impl ConfigKind {
+    fn of(value: &Value) -> Self {
+        match value {
+            Value::String(_) | Value::Datetime(_) => Self::String,
+            Value::Integer(_) => Self::Integer,
+            Value::Float(_) => Self::Float,
+            Value::Boolean(_) => Self::Boolean,
+            Value::Array(_) => Self::Array,
+            Value::Table(_) => Self::Table,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Integer => "integer",
+            Self::Float => "float",
+            Self::Boolean => "boolean",
+            Self::Array => "array",
+            Self::Table => "table",
+        }
+    }
+}
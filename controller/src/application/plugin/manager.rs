@@ -1,23 +1,72 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
-use anyhow::Result;
+use anyhow::{Result, anyhow};
+use common::error::FancyError;
 use futures::future::join_all;
-use simplelog::info;
+use simplelog::{error, info, warn};
 use tick::Ticker;
+use tokio::{spawn, sync::oneshot, task::JoinHandle};
+use url::Url;
+use uuid::Uuid;
+use watch::{PluginChange, PluginWatcher};
 
-use crate::{application::Shared, config::Config, task::manager::TaskSender};
+use crate::{
+    application::{
+        Shared,
+        node::{
+            Capabilities,
+            manager::{NodeManager, PluginNodes},
+        },
+        server::{manager::ServerManager, screen::BoxedScreen},
+    },
+    config::Config,
+    resource::DeleteResourceError,
+    task::manager::TaskSender,
+};
 
-use super::BoxedPlugin;
+use super::{BoxedNode, BoxedPlugin};
 
 #[cfg(feature = "wasm-plugins")]
-use crate::application::plugin::runtime::wasm::init::init_wasm_plugins;
+use crate::application::plugin::runtime::wasm::init::{
+    init_wasm_plugins, load_wasm_plugin_at_runtime,
+};
 
 mod tick;
+mod watch;
+
+pub type PluginLoadReceiver = oneshot::Receiver<Result<(), String>>;
 
 pub struct PluginManager {
     plugins: HashMap<String, BoxedPlugin>,
 
     ticker: Ticker,
+
+    /* Runtime loading */
+    identifier: String,
+    tasks: TaskSender,
+    shared: Arc<Shared>,
+    // Plugins are compiled and initialized in the background and swapped in once they took over the servers
+    loading: HashMap<String, PendingPlugin>,
+    watcher: Option<PluginWatcher>,
+}
+
+struct PendingPlugin {
+    stage: LoadStage,
+    replies: Vec<oneshot::Sender<Result<(), String>>>,
+}
+
+enum LoadStage {
+    Compiling(JoinHandle<Result<BoxedPlugin>>),
+    Initializing(JoinHandle<(BoxedPlugin, Result<PluginNodes>)>),
+    // The new node instances take over the servers of the old ones
+    Adopting {
+        plugin: BoxedPlugin,
+        nodes: PluginNodes,
+        screens: Vec<(Uuid, JoinHandle<Result<BoxedScreen>>)>,
+    },
 }
 
 impl PluginManager {
@@ -33,6 +82,11 @@ impl PluginManager {
         Ok(Self {
             plugins,
             ticker: Ticker::new(),
+            identifier: config.identifier().to_string(),
+            tasks: tasks.clone(),
+            shared: shared.clone(),
+            loading: HashMap::new(),
+            watcher: config.watch_plugins().then(PluginWatcher::new),
         })
     }
 
@@ -43,17 +97,251 @@ impl PluginManager {
     pub fn get_plugin(&self, name: &str) -> Option<&BoxedPlugin> {
         self.plugins.get(name)
     }
+
+    // Loads the plugin from its file or replaces it if it is already loaded
+    pub fn load(&mut self, name: &str) -> PluginLoadReceiver {
+        let (sender, receiver) = oneshot::channel();
+        if let Some(pending) = self.loading.get_mut(name) {
+            pending.replies.push(sender);
+            return receiver;
+        }
+
+        info!("Loading plugin {}...", name);
+        self.loading.insert(
+            name.to_string(),
+            PendingPlugin {
+                stage: LoadStage::Compiling(spawn_load(
+                    name.to_string(),
+                    self.identifier.clone(),
+                    self.tasks.clone(),
+                    self.shared.clone(),
+                )),
+                replies: vec![sender],
+            },
+        );
+        receiver
+    }
+
+    pub fn unload(&mut self, name: &str, nodes: &NodeManager) -> Result<(), DeleteResourceError> {
+        if nodes.is_plugin_used(name) {
+            return Err(DeleteResourceError::StillInUse);
+        }
+        let plugin = self
+            .plugins
+            .remove(name)
+            .ok_or(DeleteResourceError::NotFound)?;
+        self.drop_plugin(name, plugin, vec![], vec![]);
+        info!("Unloaded plugin {}", name);
+        Ok(())
+    }
+
+    // Nothing is awaited here, the old instances are dropped in the background once the new ones took over
+    fn install(
+        &mut self,
+        name: &str,
+        plugin: BoxedPlugin,
+        instances: PluginNodes,
+        screens: Vec<BoxedScreen>,
+        nodes: &mut NodeManager,
+    ) {
+        let replaced = nodes.install_plugin_nodes(instances);
+        if let Some(old) = self.plugins.insert(name.to_string(), plugin) {
+            self.drop_plugin(name, old, replaced, screens);
+            info!("Reloaded plugin {}", name);
+        }
+    }
+
+    fn drop_plugin(
+        &mut self,
+        name: &str,
+        plugin: BoxedPlugin,
+        nodes: Vec<BoxedNode>,
+        screens: Vec<BoxedScreen>,
+    ) {
+        let tick = self.ticker.take(name);
+        teardown(name.to_string(), plugin, nodes, screens, tick);
+    }
 }
 
 // Ticking
 impl PluginManager {
-    #[allow(clippy::unnecessary_wraps)]
-    pub async fn tick(&mut self) -> Result<()> {
+    pub async fn tick(&mut self, nodes: &mut NodeManager, servers: &ServerManager) -> Result<()> {
         self.ticker.tick(&self.plugins).await?;
+        self.tick_loading(nodes, servers).await;
+        self.tick_watcher(nodes);
         Ok(())
     }
 
+    async fn tick_loading(&mut self, nodes: &mut NodeManager, servers: &ServerManager) {
+        let names = self.loading.keys().cloned().collect::<Vec<_>>();
+        for name in names {
+            let Some(mut pending) = self.loading.remove(&name) else {
+                continue;
+            };
+            let result = match self.advance(&name, pending.stage, nodes, servers).await {
+                Ok(Some(stage)) => {
+                    pending.stage = stage;
+                    self.loading.insert(name, pending);
+                    continue;
+                }
+                Ok(None) => Ok(()),
+                Err(error) => {
+                    error!("Failed to load plugin {}: {}", name, error);
+                    FancyError::print_fancy(&error, false);
+                    Err(error.to_string())
+                }
+            };
+
+            for reply in pending.replies {
+                // The caller may not wait for the result
+                let _ = reply.send(result.clone());
+            }
+        }
+    }
+
+    // Returns the next stage or none once the plugin is installed
+    async fn advance(
+        &mut self,
+        name: &str,
+        stage: LoadStage,
+        nodes: &mut NodeManager,
+        servers: &ServerManager,
+    ) -> Result<Option<LoadStage>> {
+        match stage {
+            LoadStage::Compiling(handle) => {
+                if !handle.is_finished() {
+                    return Ok(Some(LoadStage::Compiling(handle)));
+                }
+                let plugin = handle.await??;
+                Ok(Some(LoadStage::Initializing(spawn_init(
+                    name.to_string(),
+                    plugin,
+                    nodes.plugin_node_settings(name),
+                ))))
+            }
+            LoadStage::Initializing(handle) => {
+                if !handle.is_finished() {
+                    return Ok(Some(LoadStage::Initializing(handle)));
+                }
+                let (plugin, result) = handle.await?;
+                let instances = match result {
+                    Ok(instances) => instances,
+                    Err(error) => {
+                        teardown(name.to_string(), plugin, vec![], vec![], None);
+                        return Err(error);
+                    }
+                };
+
+                // A plugin that is loaded for the first time has no servers to take over
+                if !self.plugins.contains_key(name) {
+                    self.install(name, plugin, instances, vec![], nodes);
+                    return Ok(None);
+                }
+                if let Err(error) = check_handover(name, &instances, None, nodes, servers) {
+                    teardown_instances(name.to_string(), plugin, instances, vec![]);
+                    return Err(error);
+                }
+
+                let mut screens = vec![];
+                for (node, _, instance) in &instances {
+                    for server in servers.servers_on(node) {
+                        screens.push((*server.id().uuid(), instance.adopt(server)));
+                    }
+                }
+                Ok(Some(LoadStage::Adopting {
+                    plugin,
+                    nodes: instances,
+                    screens,
+                }))
+            }
+            LoadStage::Adopting {
+                plugin,
+                nodes: instances,
+                screens,
+            } => {
+                if screens.iter().any(|(_, handle)| !handle.is_finished()) {
+                    return Ok(Some(LoadStage::Adopting {
+                        plugin,
+                        nodes: instances,
+                        screens,
+                    }));
+                }
+
+                let mut adopted = vec![];
+                let mut result = Ok(());
+                for (server, handle) in screens {
+                    match handle.await.map_err(Into::into) {
+                        Ok(Ok(screen)) => adopted.push((server, screen)),
+                        Ok(Err(error)) | Err(error) => {
+                            if result.is_ok() {
+                                result = Err(error);
+                            }
+                        }
+                    }
+                }
+                // Servers might have changed while the new instances took them over
+                let result = result.and_then(|()| {
+                    let uuids = adopted.iter().map(|(uuid, _)| *uuid).collect();
+                    check_handover(name, &instances, Some(&uuids), nodes, servers)
+                });
+                if let Err(error) = result {
+                    let screens = adopted.into_iter().map(|(_, screen)| screen).collect();
+                    teardown_instances(name.to_string(), plugin, instances, screens);
+                    return Err(error);
+                }
+
+                let mut replaced = vec![];
+                for (server, screen) in adopted {
+                    replaced.extend(self.shared.screens.replace_screen(&server, screen).await);
+                }
+                self.install(name, plugin, instances, replaced, nodes);
+                Ok(None)
+            }
+        }
+    }
+
+    fn tick_watcher(&mut self, nodes: &NodeManager) {
+        let Some(watcher) = &mut self.watcher else {
+            return;
+        };
+        let changes = match watcher.changes() {
+            Ok(changes) => changes,
+            Err(error) => {
+                warn!("Failed to check the plugins directory: {}", error);
+                return;
+            }
+        };
+
+        for change in changes {
+            match change {
+                PluginChange::Added(name) | PluginChange::Modified(name) => {
+                    drop(self.load(&name));
+                }
+                PluginChange::Removed(name) => {
+                    if !self.plugins.contains_key(&name) {
+                        continue;
+                    }
+                    match self.unload(&name, nodes) {
+                        Err(DeleteResourceError::StillInUse) => warn!(
+                            "The file of plugin {} was removed, but nodes still use it. Keeping it loaded",
+                            name
+                        ),
+                        Err(DeleteResourceError::Error(error)) => {
+                            error!("Failed to unload plugin {}: {}", name, error);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
     pub async fn cleanup(&mut self) -> Result<()> {
+        // Plugins that finish loading now would never be installed
+        for (_, pending) in self.loading.drain() {
+            pending.stage.cleanup().await?;
+        }
+
         let tasks = join_all(self.plugins.values().map(|plugin| plugin.shutdown())).await;
 
         for task in tasks {
@@ -72,3 +360,174 @@ impl PluginManager {
         Ok(())
     }
 }
+
+impl LoadStage {
+    async fn cleanup(self) -> Result<()> {
+        let (mut plugin, mut nodes, mut screens) = match self {
+            LoadStage::Compiling(handle) => match handle.await {
+                Ok(Ok(plugin)) => (plugin, vec![], vec![]),
+                _ => return Ok(()),
+            },
+            LoadStage::Initializing(handle) => match handle.await {
+                Ok((plugin, Ok(nodes))) => (plugin, nodes, vec![]),
+                Ok((plugin, Err(_))) => (plugin, vec![], vec![]),
+                Err(_) => return Ok(()),
+            },
+            LoadStage::Adopting {
+                plugin,
+                nodes,
+                screens,
+            } => {
+                let mut adopted = vec![];
+                for (_, handle) in screens {
+                    if let Ok(Ok(screen)) = handle.await {
+                        adopted.push(screen);
+                    }
+                }
+                (plugin, nodes, adopted)
+            }
+        };
+
+        // Before we can drop the plugin we have to drop the wasm resources first
+        for screen in &mut screens {
+            screen.cleanup().await?;
+        }
+        for (_, _, node) in &mut nodes {
+            node.cleanup().await?;
+        }
+        plugin.cleanup().await?;
+        drop(plugin);
+        Ok(())
+    }
+}
+
+// The servers of the nodes must not change while the new instances take them over
+fn check_handover(
+    name: &str,
+    instances: &PluginNodes,
+    adopted: Option<&HashSet<Uuid>>,
+    nodes: &NodeManager,
+    servers: &ServerManager,
+) -> Result<()> {
+    let initialized = instances
+        .iter()
+        .map(|(node, _, _)| node.as_str())
+        .collect::<HashSet<_>>();
+    if nodes
+        .get_nodes()
+        .iter()
+        .any(|node| node.plugin() == name && !initialized.contains(node.name().as_str()))
+    {
+        return Err(anyhow!(
+            "Nodes of the plugin were created while it was reloading"
+        ));
+    }
+
+    let mut servers_on_nodes = HashSet::new();
+    for node in &initialized {
+        if servers.is_node_busy(node) {
+            return Err(anyhow!(
+                "Servers on node {node} are starting, restarting or stopping. Try again once they are done"
+            ));
+        }
+        servers_on_nodes.extend(servers.servers_on(node).map(|server| *server.id().uuid()));
+    }
+    if let Some(adopted) = adopted
+        && *adopted != servers_on_nodes
+    {
+        return Err(anyhow!(
+            "Servers on the nodes of the plugin changed while they were handed over"
+        ));
+    }
+    Ok(())
+}
+
+fn spawn_init(
+    name: String,
+    plugin: BoxedPlugin,
+    loaded: HashMap<String, (Capabilities, Url)>,
+) -> JoinHandle<(BoxedPlugin, Result<PluginNodes>)> {
+    spawn(async move {
+        let result = NodeManager::init_plugin_nodes(&name, &plugin, loaded).await;
+        (plugin, result)
+    })
+}
+
+fn teardown_instances(
+    name: String,
+    plugin: BoxedPlugin,
+    instances: PluginNodes,
+    screens: Vec<BoxedScreen>,
+) {
+    let nodes = instances
+        .into_iter()
+        .map(|(_, _, instance)| instance)
+        .collect();
+    teardown(name, plugin, nodes, screens, None);
+}
+
+// A tick of the plugin may wait for the controller, so the plugin is torn down off the controller loop
+fn teardown(
+    name: String,
+    mut plugin: BoxedPlugin,
+    mut nodes: Vec<BoxedNode>,
+    mut screens: Vec<BoxedScreen>,
+    tick: Option<JoinHandle<Result<()>>>,
+) {
+    spawn(async move {
+        if let Some(tick) = tick {
+            // Errors of the last tick do not matter anymore
+            let _ = tick.await;
+        }
+
+        // Before we can drop the nodes and the plugin we have to drop the wasm resources first
+        for screen in &mut screens {
+            if let Err(error) = screen.cleanup().await {
+                warn!("Failed to drop old screen of plugin {}: {}", name, error);
+            }
+        }
+        drop(screens);
+        for node in &mut nodes {
+            if let Err(error) = node.cleanup().await {
+                warn!(
+                    "Failed to drop old node instance of plugin {}: {}",
+                    name, error
+                );
+            }
+        }
+        drop(nodes);
+
+        match plugin.shutdown().await {
+            Ok(Ok(())) => {}
+            Ok(Err(error)) => warn!("Plugin {} failed to shut down: {}", name, error),
+            Err(error) => warn!("Plugin {} failed to shut down: {}", name, error),
+        }
+        if let Err(error) = plugin.cleanup().await {
+            error!("Failed to drop plugin {}: {}", name, error);
+        }
+    });
+}
+
+#[cfg(feature = "wasm-plugins")]
+fn spawn_load(
+    name: String,
+    identifier: String,
+    tasks: TaskSender,
+    shared: Arc<Shared>,
+) -> JoinHandle<Result<BoxedPlugin>> {
+    spawn(load_wasm_plugin_at_runtime(name, identifier, tasks, shared))
+}
+
+#[cfg(not(feature = "wasm-plugins"))]
+fn spawn_load(
+    _: String,
+    _: String,
+    _: TaskSender,
+    _: Arc<Shared>,
+) -> JoinHandle<Result<BoxedPlugin>> {
+    spawn(async {
+        Err(anyhow::anyhow!(
+            "This controller was built without a plugin runtime"
+        ))
+    })
+}
@@ -37,4 +37,9 @@ impl Ticker {
         }
         Ok(())
     }
+
+    // Hands out the running tick of a plugin that is about to be dropped
+    pub fn take(&mut self, name: &str) -> Option<JoinHandle<Result<()>>> {
+        self.0.remove(name)
+    }
 }
@@ -0,0 +1,77 @@
+use std::{collections::HashMap, fs, time::SystemTime};
+
+use anyhow::Result;
+use futures::FutureExt;
+use tokio::time::{Duration, Interval, MissedTickBehavior, interval};
+
+use crate::storage::Storage;
+
+const WATCH_INTERVAL: Duration = Duration::from_secs(5);
+
+pub enum PluginChange {
+    Added(String),
+    Modified(String),
+    Removed(String),
+}
+
+// Polls the plugins directory because plugin files are replaced rarely
+pub struct PluginWatcher {
+    interval: Interval,
+    files: HashMap<String, SystemTime>,
+}
+
+impl PluginWatcher {
+    pub fn new() -> Self {
+        let mut interval = interval(WATCH_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        Self {
+            interval,
+            files: Self::scan().unwrap_or_default(),
+        }
+    }
+
+    pub fn changes(&mut self) -> Result<Vec<PluginChange>> {
+        if self.interval.tick().now_or_never().is_none() {
+            return Ok(vec![]);
+        }
+
+        let files = Self::scan()?;
+        let mut changes = vec![];
+        for (name, modified) in &files {
+            match self.files.get(name) {
+                None => changes.push(PluginChange::Added(name.clone())),
+                Some(previous) if previous != modified => {
+                    changes.push(PluginChange::Modified(name.clone()));
+                }
+                Some(_) => {}
+            }
+        }
+        for name in self.files.keys() {
+            if !files.contains_key(name) {
+                changes.push(PluginChange::Removed(name.clone()));
+            }
+        }
+        self.files = files;
+        Ok(changes)
+    }
+
+    fn scan() -> Result<HashMap<String, SystemTime>> {
+        let mut files = HashMap::new();
+        for entry in fs::read_dir(Storage::plugins_directory())? {
+            let path = entry?.path();
+            if !path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("wasm"))
+            {
+                continue;
+            }
+            if let Some(name) = path.file_stem() {
+                files.insert(
+                    name.to_string_lossy().to_string(),
+                    path.metadata()?.modified()?,
+                );
+            }
+        }
+        Ok(files)
+    }
+}
@@ -3,7 +3,10 @@ use std::sync::Arc;
 use anyhow::{Result, anyhow};
 use common::error::FancyError;
 use config::Permissions;
-use generated::{exports::plugin::system::bridge, plugin::system::data_types};
+use generated::{
+    exports::plugin::system::bridge,
+    plugin::system::{config as wit_config, data_types},
+};
 use listener::PluginListener;
 use node::PluginNode;
 use tokio::{spawn, sync::Mutex, task::JoinHandle};
@@ -16,7 +19,10 @@ use crate::{
     application::{
         Shared,
         node::Capabilities,
-        plugin::{BoxedNode, Features, GenericPlugin, Information},
+        plugin::{
+            BoxedNode, ConfigCheckHandle, Features, GenericPlugin, Information,
+            config::{ConfigField, ConfigKind, ConfigSchema},
+        },
     },
     task::manager::TaskSender,
 };
@@ -69,6 +75,9 @@ pub(crate) struct Plugin {
     // Listener
     listener: Option<Arc<Mutex<PluginListener>>>,
 
+    // Configuration
+    schema: Option<ConfigSchema>,
+
     #[allow(unused)]
     engine: Engine,
     bindings: Arc<generated::Plugin>,
@@ -179,6 +188,34 @@ impl GenericPlugin for Plugin {
         })
     }
 
+    fn config_schema(&self) -> Option<&ConfigSchema> {
+        self.schema.as_ref()
+    }
+
+    fn validate_config(&self, content: &str) -> ConfigCheckHandle {
+        let (bindings, store, instance, _) = self.get();
+        let content = content.to_string();
+        spawn(async move {
+            Ok(bindings
+                .plugin_system_bridge()
+                .plugin()
+                .call_validate_config(store.lock().await.as_context_mut(), instance, &content)
+                .await?)
+        })
+    }
+
+    fn config_changed(&self, content: &str) -> ConfigCheckHandle {
+        let (bindings, store, instance, _) = self.get();
+        let content = content.to_string();
+        spawn(async move {
+            Ok(bindings
+                .plugin_system_bridge()
+                .plugin()
+                .call_on_config_changed(store.lock().await.as_context_mut(), instance, &content)
+                .await?)
+        })
+    }
+
     async fn cleanup(&mut self) -> Result<()> {
         let mut store = self.store.lock().await;
 
@@ -214,6 +251,20 @@ impl Plugin {
             Err(error) => Err(error.into()),
         }
     }
+
+    async fn init_config_schema(&self) -> Result<Option<ConfigSchema>> {
+        let (bindings, store, instance, _) = self.get();
+        let mut store = store.lock().await;
+        match bindings
+            .plugin_system_bridge()
+            .plugin()
+            .call_config_schema(store.as_context_mut(), instance)
+            .await
+        {
+            Ok(schema) => Ok(schema.map(std::convert::Into::into)),
+            Err(error) => Err(error.into()),
+        }
+    }
 }
 
 impl Drop for Plugin {
@@ -291,3 +342,36 @@ impl From<&Capabilities> for bridge::Capabilities {
         }
     }
 }
+
+impl From<wit_config::Schema> for ConfigSchema {
+    fn from(value: wit_config::Schema) -> Self {
+        ConfigSchema::new(
+            value.file,
+            value
+                .fields
+                .into_iter()
+                .map(|field| {
+                    ConfigField::new(
+                        field.path,
+                        field.kind.into(),
+                        field.description,
+                        field.required,
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+impl From<wit_config::Kind> for ConfigKind {
+    fn from(value: wit_config::Kind) -> Self {
+        match value {
+            wit_config::Kind::String => ConfigKind::String,
+            wit_config::Kind::Integer => ConfigKind::Integer,
+            wit_config::Kind::Float => ConfigKind::Float,
+            wit_config::Kind::Boolean => ConfigKind::Boolean,
+            wit_config::Kind::Array => ConfigKind::Array,
+            wit_config::Kind::Table => ConfigKind::Table,
+        }
+    }
+}
@@ -35,7 +35,12 @@ bitflags! {
         const ALLOW_HTTP = 1 << 5;
         const ALLOW_PROCESS = 1 << 6;
         const ALLOW_REMOVE_DIR_ALL = 1 << 7;
-        const ALL = Self::INHERIT_STDIO.bits() | Self::INHERIT_ARGS.bits() | Self::INHERIT_ENV.bits() | Self::INHERIT_NETWORK.bits() | Self::ALLOW_IP_NAME_LOOKUP.bits() | Self::ALLOW_HTTP.bits() | Self::ALLOW_PROCESS.bits() | Self::ALLOW_REMOVE_DIR_ALL.bits();
+        const ALLOW_LIST_RESOURCES = 1 << 8;
+        const ALLOW_SERVER_POWER = 1 << 9;
+        const ALLOW_TRANSFER_USERS = 1 << 10;
+        const ALLOW_PUBLISH_CHANNEL = 1 << 11;
+        const ALLOW_WRITE_SCREEN = 1 << 12;
+        const ALL = Self::INHERIT_STDIO.bits() | Self::INHERIT_ARGS.bits() | Self::INHERIT_ENV.bits() | Self::INHERIT_NETWORK.bits() | Self::ALLOW_IP_NAME_LOOKUP.bits() | Self::ALLOW_HTTP.bits() | Self::ALLOW_PROCESS.bits() | Self::ALLOW_REMOVE_DIR_ALL.bits() | Self::ALLOW_LIST_RESOURCES.bits() | Self::ALLOW_SERVER_POWER.bits() | Self::ALLOW_TRANSFER_USERS.bits() | Self::ALLOW_PUBLISH_CHANNEL.bits() | Self::ALLOW_WRITE_SCREEN.bits();
     }
 }
 
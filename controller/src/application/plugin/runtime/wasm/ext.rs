@@ -10,6 +10,7 @@ use super::{
     },
 };
 
+mod control;
 mod file;
 pub mod guard;
 mod http;
@@ -22,6 +23,7 @@ mod tls;
 
 impl system::types::Host for PluginState {}
 impl system::data_types::Host for PluginState {}
+impl system::config::Host for PluginState {}
 
 impl PluginState {
     pub fn get_directory(name: &str, directory: &Directory) -> PathBuf {
@@ -0,0 +1,384 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Result, anyhow};
+use simplelog::warn;
+use tokio::spawn;
+use tonic::{Status, async_trait};
+use uuid::Uuid;
+use wasmtime::ToWasmtimeResult;
+
+use crate::{
+    application::{
+        Controller,
+        channel::{ChannelAccess, pattern::is_pattern},
+        node::LifecycleStatus,
+        plugin::runtime::wasm::{
+            PluginState,
+            config::Permissions,
+            generated::plugin::system::{
+                control::{self, Group, ServerRequest, TransferTarget as WitTransferTarget},
+                data_types::{self, Server},
+                types::ErrorMessage,
+            },
+        },
+        server::{
+            DiskRetention, FallbackPolicy, Resources, Specification,
+            manager::{StartRequest, StopRequest},
+            screen::ScreenWriteJoinHandle,
+            selector::ServerSelector,
+        },
+        user::{
+            CurrentServer,
+            transfer::{Transfer, TransferReporter, TransferTarget},
+        },
+    },
+    network::client::ChannelMsg,
+    task::{BoxedAny, GenericTask, plugin::PluginTask},
+};
+
+impl control::Host for PluginState {
+    async fn get_groups(&mut self) -> wasmtime::Result<Result<Vec<Group>, ErrorMessage>> {
+        self.require(Permissions::ALLOW_LIST_RESOURCES, "list groups")?;
+
+        Ok(Ok(PluginTask::execute::<Vec<Group>, _>(
+            &self.tasks,
+            GetGroupsTask,
+        )
+        .await
+        .to_wasmtime_result()?))
+    }
+
+    async fn get_servers(
+        &mut self,
+        group: Option<String>,
+    ) -> wasmtime::Result<Result<Vec<Server>, ErrorMessage>> {
+        self.require(Permissions::ALLOW_LIST_RESOURCES, "list servers")?;
+
+        Ok(Ok(PluginTask::execute::<Vec<Server>, _>(
+            &self.tasks,
+            GetServersTask(group),
+        )
+        .await
+        .to_wasmtime_result()?))
+    }
+
+    async fn schedule_server(
+        &mut self,
+        request: ServerRequest,
+    ) -> wasmtime::Result<Result<String, ErrorMessage>> {
+        self.require(Permissions::ALLOW_SERVER_POWER, "schedule a server")?;
+
+        if request.name.is_empty() || request.node.is_empty() {
+            return Ok(Err("A server needs a name and a node".to_string()));
+        }
+
+        Ok(Ok(PluginTask::execute::<String, _>(
+            &self.tasks,
+            ScheduleServerTask(request),
+        )
+        .await
+        .to_wasmtime_result()?))
+    }
+
+    async fn stop_server(&mut self, server: String) -> wasmtime::Result<Result<(), ErrorMessage>> {
+        self.require(Permissions::ALLOW_SERVER_POWER, "stop a server")?;
+
+        let Ok(server) = Uuid::parse_str(&server) else {
+            return Ok(Err("Failed to parse provided uuid".to_string()));
+        };
+
+        PluginTask::execute::<Result<(), ErrorMessage>, _>(&self.tasks, StopServerTask(server))
+            .await
+            .to_wasmtime_result()
+    }
+
+    async fn transfer_users(
+        &mut self,
+        users: Vec<String>,
+        target: WitTransferTarget,
+    ) -> wasmtime::Result<Result<u32, ErrorMessage>> {
+        self.require(Permissions::ALLOW_TRANSFER_USERS, "transfer users")?;
+
+        let Ok(users) = users
+            .iter()
+            .map(|user| Uuid::parse_str(user))
+            .collect::<Result<Vec<_>, _>>()
+        else {
+            return Ok(Err("Failed to parse provided uuid".to_string()));
+        };
+        let target = match target {
+            WitTransferTarget::Server(server) => {
+                let Ok(server) = Uuid::parse_str(&server) else {
+                    return Ok(Err("Failed to parse provided uuid".to_string()));
+                };
+                TransferTarget::Server(server)
+            }
+            WitTransferTarget::Group(group) => TransferTarget::Group(group),
+            WitTransferTarget::Fallback => TransferTarget::Fallback,
+        };
+
+        PluginTask::execute::<Result<u32, ErrorMessage>, _>(
+            &self.tasks,
+            TransferUsersTask(users, target),
+        )
+        .await
+        .to_wasmtime_result()
+    }
+
+    #[allow(clippy::unused_async_trait_impl)]
+    async fn publish_message(
+        &mut self,
+        channel: String,
+        data: Vec<u8>,
+    ) -> wasmtime::Result<Result<(), ErrorMessage>> {
+        self.require(Permissions::ALLOW_PUBLISH_CHANNEL, "publish a message")?;
+
+        if is_pattern(&channel) {
+            return Ok(Err(
+                "Messages can not be published to a channel pattern".to_string()
+            ));
+        }
+
+        // The sequence is assigned by the channel itself
+        let message = ChannelMsg {
+            channel,
+            data,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| {
+                    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+                }),
+            sequence: 0,
+        };
+        // The plugin holds its store while calling us and may be subscribed to the channel itself
+        let tasks = self.tasks.clone();
+        spawn(async move {
+            match PluginTask::execute::<Result<u32, Status>, _>(&tasks, PublishMessageTask(message))
+                .await
+            {
+                Ok(Ok(_)) => {}
+                Ok(Err(error)) => warn!("Failed to publish message of plugin: {}", error.message()),
+                Err(error) => warn!("Failed to publish message of plugin: {}", error),
+            }
+        });
+        Ok(Ok(()))
+    }
+
+    #[allow(clippy::unused_async_trait_impl)]
+    async fn write_to_screen(
+        &mut self,
+        server: String,
+        data: Vec<u8>,
+    ) -> wasmtime::Result<Result<(), ErrorMessage>> {
+        self.require(Permissions::ALLOW_WRITE_SCREEN, "write to a screen")?;
+
+        let Ok(server) = Uuid::parse_str(&server) else {
+            return Ok(Err("Failed to parse provided uuid".to_string()));
+        };
+
+        // The screen may belong to this plugin, whose store is locked until we return
+        let tasks = self.tasks.clone();
+        spawn(async move {
+            let result = match PluginTask::execute::<Result<ScreenWriteJoinHandle, Status>, _>(
+                &tasks,
+                WriteToScreenTask(server, data),
+            )
+            .await
+            {
+                Ok(Ok(handle)) => match handle.await {
+                    Ok(Ok(())) => return,
+                    Ok(Err(error)) => Status::from(error).message().to_string(),
+                    Err(error) => error.to_string(),
+                },
+                Ok(Err(error)) => error.message().to_string(),
+                Err(error) => error.to_string(),
+            };
+            warn!(
+                "Failed to write to screen of server {} for plugin: {}",
+                server, result
+            );
+        });
+        Ok(Ok(()))
+    }
+}
+
+impl PluginState {
+    // Missing permissions trap the plugin instead of returning an error
+    fn require(&self, permission: Permissions, action: &str) -> wasmtime::Result<()> {
+        if !self.permissions.contains(permission) {
+            return Err(anyhow!(
+                "Plugin tried to {action} without the required permissions"
+            ))
+            .to_wasmtime_result();
+        }
+        Ok(())
+    }
+}
+
+pub struct GetGroupsTask;
+pub struct GetServersTask(pub Option<String>);
+pub struct ScheduleServerTask(pub ServerRequest);
+pub struct StopServerTask(pub Uuid);
+pub struct TransferUsersTask(pub Vec<Uuid>, pub TransferTarget);
+pub struct PublishMessageTask(pub ChannelMsg);
+pub struct WriteToScreenTask(pub Uuid, pub Vec<u8>);
+
+#[async_trait]
+impl GenericTask for GetGroupsTask {
+    async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
+        PluginTask::new_ok(
+            controller
+                .groups
+                .get_groups()
+                .into_iter()
+                .map(|group| Group {
+                    name: group.name().clone(),
+                    active: *group.status() == LifecycleStatus::Active,
+                })
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+#[async_trait]
+impl GenericTask for GetServersTask {
+    async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
+        PluginTask::new_ok(
+            controller
+                .servers
+                .select_servers(
+                    &self
+                        .0
+                        .clone()
+                        .map_or_else(ServerSelector::default, ServerSelector::group),
+                )
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<Server>>(),
+        )
+    }
+}
+
+#[async_trait]
+impl GenericTask for ScheduleServerTask {
+    async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
+        let request = &self.0;
+        let specification = &request.specification;
+        let request = StartRequest::new(
+            None,
+            request.priority,
+            request.name.clone(),
+            None,
+            std::slice::from_ref(&request.node),
+            &(&request.resources).into(),
+            &Specification::new(
+                specification.settings.iter().cloned().collect(),
+                specification.environment.iter().cloned().collect(),
+                (&specification.disk_retention).into(),
+                specification.image.clone(),
+                request.max_players,
+                FallbackPolicy::default(),
+                ChannelAccess::default(),
+            ),
+        );
+        let uuid = request.id().uuid().to_string();
+        controller.servers.schedule_start(request);
+
+        PluginTask::new_ok(uuid)
+    }
+}
+
+#[async_trait]
+impl GenericTask for StopServerTask {
+    async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
+        let Some(server) = controller.servers.get_server(&self.0) else {
+            return PluginTask::new_ok(Err::<(), ErrorMessage>("Server not found".to_string()));
+        };
+
+        controller
+            .servers
+            .schedule_stop(StopRequest::new(None, server.id().clone()));
+        PluginTask::new_ok(Ok::<(), ErrorMessage>(()))
+    }
+}
+
+#[async_trait]
+impl GenericTask for TransferUsersTask {
+    async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
+        let mut started = 0;
+        for uuid in &self.0 {
+            let Some(user) = controller.users.get_user_mut(uuid) else {
+                continue;
+            };
+            // Only users that are on a server can be moved
+            let CurrentServer::Connected(from) = user.server() else {
+                continue;
+            };
+            let from = from.clone();
+            let Ok(to) = Transfer::resolve_target(
+                &self.1,
+                &[*from.uuid()],
+                &controller.servers,
+                &controller.groups,
+                1,
+            ) else {
+                continue;
+            };
+
+            let target = *to.id().uuid();
+            let mut transfer =
+                Transfer::new(Uuid::new_v4(), user, from, to, TransferReporter::default());
+            if Transfer::transfer_user(&mut transfer, &controller.shared)
+                .await
+                .is_err()
+            {
+                continue;
+            }
+            started += 1;
+            // Hold the slot until the user arrives
+            if let Some(server) = controller.servers.get_server_mut(&target) {
+                server.reserve_slots(1);
+            }
+        }
+        PluginTask::new_ok(Ok::<u32, ErrorMessage>(started))
+    }
+}
+
+#[async_trait]
+impl GenericTask for PublishMessageTask {
+    async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
+        PluginTask::new_ok(
+            controller
+                .shared
+                .channels
+                .publish(
+                    controller.shared.subscribers.network().channel(),
+                    self.0.clone(),
+                )
+                .await,
+        )
+    }
+}
+
+#[async_trait]
+impl GenericTask for WriteToScreenTask {
+    async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
+        // Only starts the write, the controller does not wait for the plugin behind the screen
+        PluginTask::new_ok(controller.shared.screens.write(&self.0, &self.1).await)
+    }
+}
+
+impl From<&data_types::Resources> for Resources {
+    fn from(val: &data_types::Resources) -> Self {
+        Resources::new(val.memory, val.swap, val.cpu, val.io, val.disk, val.ports)
+    }
+}
+
+impl From<&data_types::DiskRetention> for DiskRetention {
+    fn from(val: &data_types::DiskRetention) -> Self {
+        match val {
+            data_types::DiskRetention::Permanent => DiskRetention::Permanent,
+            data_types::DiskRetention::Temporary => DiskRetention::Temporary,
+        }
+    }
+}
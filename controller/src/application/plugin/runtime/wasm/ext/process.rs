@@ -212,6 +212,9 @@ impl system::process::HostProcess for PluginState {
         }
         Ok(lines)
     }
+    async fn has_output(&mut self, instance: Resource<Process>) -> wasmtime::Result<bool> {
+        Ok(!self.resources.get(&instance)?.streams.receiver.is_empty())
+    }
     async fn write_all(
         &mut self,
         instance: Resource<Process>,
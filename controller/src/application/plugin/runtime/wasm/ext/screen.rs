@@ -4,16 +4,29 @@ use anyhow::{Result, anyhow};
 use common::error::FancyError;
 use tokio::{spawn, sync::Mutex};
 use tonic::async_trait;
+use uuid::Uuid;
 use wasmtime::{AsContextMut, Store, component::ResourceAny};
 
 use crate::application::{
     plugin::runtime::wasm::{
         PluginState,
-        generated::{self, exports::plugin::system::screen::ScreenType},
+        generated::{
+            self, exports::plugin::system::screen::ScreenType, plugin::system::screen_events,
+        },
     },
     server::screen::{GenericScreen, ScreenError, ScreenPullJoinHandle, ScreenWriteJoinHandle},
 };
 
+impl screen_events::Host for PluginState {
+    async fn output_available(&mut self, server: String) -> wasmtime::Result<()> {
+        // Unknown servers are ignored, at worst this causes a pull that returns nothing
+        if let Ok(server) = Uuid::parse_str(&server) {
+            self.shared.screens.notify(server);
+        }
+        Ok(())
+    }
+}
+
 pub struct PluginScreen {
     dropped: bool,
 
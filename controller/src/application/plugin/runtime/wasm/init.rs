@@ -1,6 +1,6 @@
 use std::{collections::HashMap, path::Path, sync::Arc};
 
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use common::error::FancyError;
 use simplelog::{debug, error, info, warn};
 use tokio::{fs, sync::Mutex};
@@ -27,7 +27,6 @@ use super::{
     generated,
 };
 
-#[allow(clippy::too_many_lines)]
 pub async fn init_wasm_plugins(
     global_config: &Config,
     tasks: &TaskSender,
@@ -46,112 +45,27 @@ pub async fn init_wasm_plugins(
     let amount = plugins.len();
     let mut invoker = EpochInvoker::new();
     for (path, _, name) in Storage::for_each_content(&directory).await? {
-        if !path
-            .extension()
-            .is_some_and(|ext| ext.eq_ignore_ascii_case("wasm"))
-        {
+        if !is_wasm_file(&path) {
             continue;
         }
 
-        let source = match Source::from_file(&path) {
-            Ok(source) => source,
-            Err(error) => {
-                error!(
-                    "Failed to read source code for plugin {} from file({:?}): {}",
-                    name, path, error
-                );
-                continue;
-            }
-        };
-
-        let config_directory = Storage::config_directory_for_plugin(&name);
-        let data_directory = Storage::data_directory_for_plugin(&name);
-        if !config_directory.exists() {
-            fs::create_dir_all(&config_directory)
-                .await
-                .unwrap_or_else(|error| {
-                    warn!(
-                        "Failed to create configs directory for plugin {}: {}",
-                        name, error
-                    );
-                });
-        }
-        if !data_directory.exists() {
-            fs::create_dir_all(&data_directory)
-                .await
-                .unwrap_or_else(|error| {
-                    warn!(
-                        "Failed to create data directory for plugin {}: {}",
-                        name, error
-                    );
-                });
-        }
-
-        info!("Compiling plugin {}...", name);
-        let plugin = Plugin::new(
+        match load_wasm_plugin(
             &name,
-            &source,
-            global_config,
-            tasks.clone(),
-            shared.clone(),
+            &path,
+            global_config.identifier(),
+            tasks,
+            shared,
             &plugins_config,
-            &data_directory,
-            &config_directory,
             &mut invoker,
         )
-        .await;
-        match plugin {
-            Ok(mut plugin) => match plugin.init().await {
-                Ok(information) => {
-                    if information.ready {
-                        info!(
-                            "Loaded plugin {} v{} by {}",
-                            name,
-                            information.version,
-                            information.authors.join(", ")
-                        );
-                        plugin.features = information.features;
-
-                        // Initialize the plugin listener
-                        if plugin.features.contains(Features::LISTENER) {
-                            match plugin.init_listener().await {
-                                Ok(mut listener) => {
-                                    listener.register(shared).await;
-                                    debug!(
-                                        "The plugin {} now listens to the specified events",
-                                        name
-                                    );
-                                    plugin.listener = Some(Arc::new(Mutex::new(listener)));
-                                }
-                                Err(error) => {
-                                    error!(
-                                        "Failed to initialize listener for plugin {}: {}",
-                                        name, error
-                                    );
-                                    FancyError::print_fancy(&error, false);
-                                }
-                            }
-                        }
-
-                        plugins.insert(name, Box::new(plugin));
-                    } else {
-                        warn!("Plugin {} marked itself as not ready, skipping...", name);
-                        if let Err(error) = plugin.cleanup().await {
-                            error!("Failed to drop resources for plugin {}: {}", name, error);
-                            FancyError::print_fancy(&error, false);
-                        }
-                    }
-                }
-                Err(error) => {
-                    error!("Failed to initialize plugin {}: {}", name, error);
-                    FancyError::print_fancy(&error, false);
-                }
-            },
+        .await
+        {
+            Ok(Some(plugin)) => {
+                plugins.insert(name, plugin);
+            }
+            Ok(None) => {}
             Err(error) => {
-                error!(
-                    "Failed to compile plugin {} at location {}: {}",
-                    name, source, error
-                );
+                error!("Failed to load plugin {}: {}", name, error);
                 FancyError::print_fancy(&error, false);
             }
         }
@@ -166,12 +80,160 @@ pub async fn init_wasm_plugins(
     Ok(())
 }
 
+// Loads a single plugin while the controller is already running
+pub async fn load_wasm_plugin_at_runtime(
+    name: String,
+    identifier: String,
+    tasks: TaskSender,
+    shared: Arc<Shared>,
+) -> Result<BoxedPlugin> {
+    verify_engine_config().await?;
+    let plugins_config = PluginsConfig::parse().await?;
+
+    let path = Storage::wasm_plugin_file(&name);
+    if !path.exists() {
+        return Err(anyhow!("No plugin file found at {}", path.display()));
+    }
+
+    let mut invoker = EpochInvoker::new();
+    let plugin = load_wasm_plugin(
+        &name,
+        &path,
+        &identifier,
+        &tasks,
+        &shared,
+        &plugins_config,
+        &mut invoker,
+    )
+    .await?
+    .ok_or_else(|| anyhow!("Plugin {name} marked itself as not ready"))?;
+    invoker.spawn();
+
+    Ok(plugin)
+}
+
+fn is_wasm_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("wasm"))
+}
+
+// Returns None if the plugin marked itself as not ready
+async fn load_wasm_plugin(
+    name: &str,
+    path: &Path,
+    identifier: &str,
+    tasks: &TaskSender,
+    shared: &Arc<Shared>,
+    plugins_config: &PluginsConfig,
+    invoker: &mut EpochInvoker,
+) -> Result<Option<BoxedPlugin>> {
+    let source = Source::from_file(path).map_err(|error| {
+        anyhow!(
+            "Failed to read source code from file({}): {error}",
+            path.display()
+        )
+    })?;
+
+    let config_directory = Storage::config_directory_for_plugin(name);
+    let data_directory = Storage::data_directory_for_plugin(name);
+    if !config_directory.exists() {
+        fs::create_dir_all(&config_directory)
+            .await
+            .unwrap_or_else(|error| {
+                warn!(
+                    "Failed to create configs directory for plugin {}: {}",
+                    name, error
+                );
+            });
+    }
+    if !data_directory.exists() {
+        fs::create_dir_all(&data_directory)
+            .await
+            .unwrap_or_else(|error| {
+                warn!(
+                    "Failed to create data directory for plugin {}: {}",
+                    name, error
+                );
+            });
+    }
+
+    info!("Compiling plugin {}...", name);
+    let mut plugin = Plugin::new(
+        name,
+        &source,
+        identifier,
+        tasks.clone(),
+        shared.clone(),
+        plugins_config,
+        &data_directory,
+        &config_directory,
+        invoker,
+    )
+    .await
+    .map_err(|error| anyhow!("Failed to compile plugin at location {source}: {error}"))?;
+
+    let information = match plugin.init().await {
+        Ok(information) => information,
+        Err(error) => {
+            if let Err(error) = plugin.cleanup().await {
+                error!("Failed to drop resources for plugin {}: {}", name, error);
+            }
+            return Err(anyhow!("Failed to initialize plugin: {error}"));
+        }
+    };
+    if !information.ready {
+        warn!("Plugin {} marked itself as not ready, skipping...", name);
+        if let Err(error) = plugin.cleanup().await {
+            error!("Failed to drop resources for plugin {}: {}", name, error);
+            FancyError::print_fancy(&error, false);
+        }
+        return Ok(None);
+    }
+
+    info!(
+        "Loaded plugin {} v{} by {}",
+        name,
+        information.version,
+        information.authors.join(", ")
+    );
+    plugin.features = information.features;
+
+    // Initialize the plugin listener
+    if plugin.features.contains(Features::LISTENER) {
+        match plugin.init_listener().await {
+            Ok(mut listener) => {
+                listener.register(shared).await;
+                debug!("The plugin {} now listens to the specified events", name);
+                plugin.listener = Some(Arc::new(Mutex::new(listener)));
+            }
+            Err(error) => {
+                error!(
+                    "Failed to initialize listener for plugin {}: {}",
+                    name, error
+                );
+                FancyError::print_fancy(&error, false);
+            }
+        }
+    }
+
+    // Plugins without a schema keep reading their config files on their own
+    match plugin.init_config_schema().await {
+        Ok(schema) => plugin.schema = schema,
+        Err(error) => {
+            error!("Failed to read config schema of plugin {}: {}", name, error);
+            FancyError::print_fancy(&error, false);
+        }
+    }
+
+    Ok(Some(Box::new(plugin)))
+}
+
 impl Plugin {
     #[allow(clippy::too_many_arguments)]
     async fn new(
         name: &str,
         source: &Source,
-        global_config: &Config,
+        identifier: &str,
         tasks: TaskSender,
         shared: Arc<Shared>,
         plugins_config: &PluginsConfig,
@@ -268,7 +330,7 @@ impl Plugin {
         let instance = bindings
             .plugin_system_bridge()
             .plugin()
-            .call_constructor(&mut store, global_config.identifier())
+            .call_constructor(&mut store, identifier)
             .await?;
 
         // Start thread that calls the increment epoch function
@@ -278,6 +340,7 @@ impl Plugin {
             dropped: false,
             features: Features::empty(),
             listener: None,
+            schema: None,
             engine,
             bindings: Arc::new(bindings),
             store: Arc::new(Mutex::new(store)),
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{hash::Hash, sync::Arc};
 
 use anyhow::{Result, anyhow};
 use common::error::FancyError;
@@ -6,18 +6,33 @@ use futures::FutureExt;
 use tokio::sync::{MutexGuard, mpsc::Receiver};
 use wasmtime::{AsContextMut, Store, component::ResourceAny};
 
-use crate::application::{
-    Shared,
-    subscriber::{
-        Subscriber,
-        manager::event::server::{ServerEvent, ServerReadyEvent},
+use crate::{
+    application::{
+        Shared,
+        server::NameAndUuid,
+        subscriber::{
+            Subscriber,
+            manager::event::{
+                group::{GroupActiveEvent, GroupScaledEvent},
+                node::NodeActiveEvent,
+                server::{ServerEvent, ServerReadyEvent},
+                user::{UserEvent, UserTransferEvent},
+            },
+            watcher::Watcher,
+        },
     },
+    network::client::ChannelMsg,
 };
 
 use super::{
     PluginState,
     generated::{
-        self, exports::plugin::system::event::Events, plugin::system::types::ErrorMessage,
+        self,
+        exports::plugin::system::event::Events,
+        plugin::system::{
+            data_types::{ChannelMessage, Identity},
+            types::ErrorMessage,
+        },
     },
 };
 
@@ -31,6 +46,13 @@ pub struct PluginListener {
     server_start: Option<Receiver<Result<ServerEvent>>>,
     server_stop: Option<Receiver<Result<ServerEvent>>>,
     server_change_ready: Option<Receiver<Result<ServerReadyEvent>>>,
+    user_connected: Option<Receiver<Result<UserEvent>>>,
+    user_disconnected: Option<Receiver<Result<UserEvent>>>,
+    user_transferred: Option<Receiver<Result<UserTransferEvent>>>,
+    group_change_active: Option<Receiver<Result<GroupActiveEvent>>>,
+    group_scaled: Option<Receiver<Result<GroupScaledEvent>>>,
+    node_change_active: Option<Receiver<Result<NodeActiveEvent>>>,
+    channel_message: Option<Receiver<Result<ChannelMsg>>>,
 }
 
 impl PluginListener {
@@ -43,42 +65,58 @@ impl PluginListener {
             server_start: None,
             server_stop: None,
             server_change_ready: None,
+            user_connected: None,
+            user_disconnected: None,
+            user_transferred: None,
+            group_change_active: None,
+            group_scaled: None,
+            node_change_active: None,
+            channel_message: None,
         }
     }
 
     pub async fn register(&mut self, shared: &Arc<Shared>) {
+        let events = shared.subscribers.plugin();
         if self.events.contains(Events::SERVER_START) {
-            let (subscriber, receiver) = Subscriber::create_plugin();
-            shared
-                .subscribers
-                .plugin()
-                .server_start()
-                .subscribe(subscriber)
-                .await;
-            self.server_start = Some(receiver);
+            self.server_start = Some(Self::subscribe(events.server_start()).await);
         }
         if self.events.contains(Events::SERVER_STOP) {
-            let (subscriber, receiver) = Subscriber::create_plugin();
-            shared
-                .subscribers
-                .plugin()
-                .server_stop()
-                .subscribe(subscriber)
-                .await;
-            self.server_stop = Some(receiver);
+            self.server_stop = Some(Self::subscribe(events.server_stop()).await);
         }
         if self.events.contains(Events::SERVER_CHANGE_READY) {
-            let (subscriber, receiver) = Subscriber::create_plugin();
-            shared
-                .subscribers
-                .plugin()
-                .server_change_ready()
-                .subscribe(subscriber)
-                .await;
-            self.server_change_ready = Some(receiver);
+            self.server_change_ready = Some(Self::subscribe(events.server_change_ready()).await);
+        }
+        if self.events.contains(Events::USER_CONNECTED) {
+            self.user_connected = Some(Self::subscribe(events.user_connected()).await);
+        }
+        if self.events.contains(Events::USER_DISCONNECTED) {
+            self.user_disconnected = Some(Self::subscribe(events.user_disconnected()).await);
+        }
+        if self.events.contains(Events::USER_TRANSFERRED) {
+            self.user_transferred = Some(Self::subscribe(events.user_transferred()).await);
+        }
+        if self.events.contains(Events::GROUP_CHANGE_ACTIVE) {
+            self.group_change_active = Some(Self::subscribe(events.group_change_active()).await);
+        }
+        if self.events.contains(Events::GROUP_SCALED) {
+            self.group_scaled = Some(Self::subscribe(events.group_scaled()).await);
+        }
+        if self.events.contains(Events::NODE_CHANGE_ACTIVE) {
+            self.node_change_active = Some(Self::subscribe(events.node_change_active()).await);
+        }
+        if self.events.contains(Events::CHANNEL_MESSAGE) {
+            // Unscoped subscribers receive the messages of every channel
+            self.channel_message =
+                Some(Self::subscribe(shared.subscribers.network().channel()).await);
         }
     }
 
+    async fn subscribe<A: Eq + Hash, T: Clone>(watcher: &Watcher<A, T>) -> Receiver<Result<T>> {
+        let (subscriber, receiver) = Subscriber::create_plugin();
+        watcher.subscribe(subscriber).await;
+        receiver
+    }
+
     fn collect_events<T>(event: &mut Option<Receiver<Result<T>>>) -> Vec<T> {
         let mut events = Vec::new();
         if let Some(receiver) = event.as_mut() {
@@ -113,6 +151,16 @@ impl PluginListener {
         &mut self,
         bindings: &Arc<generated::Plugin>,
         store: &mut MutexGuard<'_, Store<PluginState>>,
+    ) {
+        self.fire_server_events(bindings, store).await;
+        self.fire_user_events(bindings, store).await;
+        self.fire_resource_events(bindings, store).await;
+    }
+
+    async fn fire_server_events(
+        &mut self,
+        bindings: &Arc<generated::Plugin>,
+        store: &mut MutexGuard<'_, Store<PluginState>>,
     ) {
         for event in Self::collect_events(&mut self.server_start) {
             let event = event.into();
@@ -154,6 +202,109 @@ impl PluginListener {
         }
     }
 
+    async fn fire_user_events(
+        &mut self,
+        bindings: &Arc<generated::Plugin>,
+        store: &mut MutexGuard<'_, Store<PluginState>>,
+    ) {
+        for event in Self::collect_events(&mut self.user_connected) {
+            let user = event.user.into();
+            let server = event.server.into();
+            Self::handle_result(
+                bindings
+                    .plugin_system_event()
+                    .listener()
+                    .call_user_connected(store.as_context_mut(), self.instance, &user, &server)
+                    .await
+                    .map_err(std::convert::Into::into),
+            );
+        }
+        for event in Self::collect_events(&mut self.user_disconnected) {
+            let user = event.user.into();
+            let server = event.server.into();
+            Self::handle_result(
+                bindings
+                    .plugin_system_event()
+                    .listener()
+                    .call_user_disconnected(store.as_context_mut(), self.instance, &user, &server)
+                    .await
+                    .map_err(std::convert::Into::into),
+            );
+        }
+        for event in Self::collect_events(&mut self.user_transferred) {
+            let user = event.user.into();
+            let from = event.from.into();
+            let to = event.to.into();
+            Self::handle_result(
+                bindings
+                    .plugin_system_event()
+                    .listener()
+                    .call_user_transferred(store.as_context_mut(), self.instance, &user, &from, &to)
+                    .await
+                    .map_err(std::convert::Into::into),
+            );
+        }
+    }
+
+    // Groups, nodes and channels
+    async fn fire_resource_events(
+        &mut self,
+        bindings: &Arc<generated::Plugin>,
+        store: &mut MutexGuard<'_, Store<PluginState>>,
+    ) {
+        for event in Self::collect_events(&mut self.group_change_active) {
+            Self::handle_result(
+                bindings
+                    .plugin_system_event()
+                    .listener()
+                    .call_group_change_active(
+                        store.as_context_mut(),
+                        self.instance,
+                        &event.0,
+                        event.1,
+                    )
+                    .await
+                    .map_err(std::convert::Into::into),
+            );
+        }
+        for event in Self::collect_events(&mut self.group_scaled) {
+            Self::handle_result(
+                bindings
+                    .plugin_system_event()
+                    .listener()
+                    .call_group_scaled(store.as_context_mut(), self.instance, &event.0, event.1)
+                    .await
+                    .map_err(std::convert::Into::into),
+            );
+        }
+        for event in Self::collect_events(&mut self.node_change_active) {
+            Self::handle_result(
+                bindings
+                    .plugin_system_event()
+                    .listener()
+                    .call_node_change_active(
+                        store.as_context_mut(),
+                        self.instance,
+                        &event.0,
+                        event.1,
+                    )
+                    .await
+                    .map_err(std::convert::Into::into),
+            );
+        }
+        for event in Self::collect_events(&mut self.channel_message) {
+            let message = event.into();
+            Self::handle_result(
+                bindings
+                    .plugin_system_event()
+                    .listener()
+                    .call_channel_message(store.as_context_mut(), self.instance, &message)
+                    .await
+                    .map_err(std::convert::Into::into),
+            );
+        }
+    }
+
     pub async fn cleanup(&mut self, store: impl AsContextMut<Data = PluginState>) -> Result<()> {
         self.instance.resource_drop_async(store).await?;
         self.dropped = true;
@@ -172,3 +323,23 @@ impl Drop for PluginListener {
         }
     }
 }
+
+impl From<NameAndUuid> for Identity {
+    fn from(value: NameAndUuid) -> Self {
+        Identity {
+            name: value.name().clone(),
+            uuid: value.uuid().to_string(),
+        }
+    }
+}
+
+impl From<ChannelMsg> for ChannelMessage {
+    fn from(value: ChannelMsg) -> Self {
+        ChannelMessage {
+            channel: value.channel,
+            data: value.data,
+            timestamp: value.timestamp,
+            sequence: value.sequence,
+        }
+    }
+}
@@ -175,6 +175,28 @@ impl GenericNode for PluginNode {
         })
     }
 
+    fn adopt(&self, server: &Server) -> JoinHandle<Result<BoxedScreen>> {
+        let server = server.into();
+
+        let (bindings, store, instance) = self.get();
+        spawn(async move {
+            match bindings
+                .plugin_system_bridge()
+                .node()
+                .call_adopt(store.lock().await.as_context_mut(), instance, &server)
+                .await
+            {
+                Ok(result) => result
+                    .map(|screen| {
+                        Box::new(PluginScreen::new(bindings.clone(), store.clone(), screen))
+                            as BoxedScreen
+                    })
+                    .map_err(|error| anyhow!(error)),
+                Err(error) => Err(error.into()),
+            }
+        })
+    }
+
     async fn cleanup(&mut self) -> Result<()> {
         self.instance
             .resource_drop_async(self.store.lock().await.as_context_mut())
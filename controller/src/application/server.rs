@@ -12,11 +12,12 @@ use uuid::Uuid;
 
 use crate::network::client::TransferMsg;
 
-use super::{Shared, node::Allocation};
+use super::{Shared, channel::ChannelAccess, node::Allocation, user::User};
 
 pub mod guard;
 pub mod manager;
 pub mod screen;
+pub mod selector;
 
 #[derive(Getters, Setters, MutGetters)]
 pub struct Server {
@@ -35,6 +36,9 @@ pub struct Server {
     /* Users */
     #[getset(get = "pub", set = "pub")]
     connected_users: u32,
+    // Slots held for users that are on their way to this server
+    #[getset(get = "pub")]
+    reserved: u32,
 
     /* States */
     #[getset(get = "pub", get_mut = "pub")]
@@ -111,6 +115,10 @@ pub struct Specification {
     max_players: u32,
     #[getset(get = "pub")]
     fallback: FallbackPolicy,
+
+    #[serde(default)]
+    #[getset(get = "pub")]
+    channels: ChannelAccess,
 }
 
 pub struct Heart {
@@ -119,15 +127,34 @@ pub struct Heart {
 }
 
 impl Server {
-    pub fn new_transfer(&self, user: &Uuid) -> Option<TransferMsg> {
+    pub fn new_transfer(&self, transfer: &Uuid, user: &User) -> Option<TransferMsg> {
         let port = self.allocation.primary_port()?;
         Some(TransferMsg {
-            id: user.to_string(),
+            id: user.id().uuid().to_string(),
             host: port.host.clone(),
             port: u32::from(port.port),
+            data: user.data().into(),
+            transfer: transfer.to_string(),
         })
     }
 
+    // Ready, running and able to take the users on top of the reserved slots
+    pub fn has_free_slots(&self, slots: u32) -> bool {
+        self.ready && self.state == State::Running && self.fits(slots)
+    }
+
+    pub fn fits(&self, slots: u32) -> bool {
+        self.connected_users + self.reserved + slots <= self.allocation.specification.max_players
+    }
+
+    pub fn reserve_slots(&mut self, slots: u32) {
+        self.reserved += slots;
+    }
+
+    pub fn release_slot(&mut self) {
+        self.reserved = self.reserved.saturating_sub(1);
+    }
+
     pub async fn set_ready(&mut self, ready: bool, shared: &Arc<Shared>) {
         if self.ready != ready {
             self.ready = ready;
@@ -223,6 +250,7 @@ impl Specification {
         image: String,
         max_players: u32,
         fallback: FallbackPolicy,
+        channels: ChannelAccess,
     ) -> Self {
         Self {
             settings,
@@ -231,6 +259,7 @@ impl Specification {
             image,
             max_players,
             fallback,
+            channels,
         }
     }
 }
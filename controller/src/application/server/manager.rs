@@ -1,4 +1,5 @@
 use std::{
+    cmp::Reverse,
     collections::{BinaryHeap, HashMap},
     sync::Arc,
 };
@@ -20,6 +21,7 @@ use crate::{
 
 use super::{
     NameAndUuid, Resources, Server, Specification, State, guard::WeakGuard, screen::BoxedScreen,
+    selector::ServerSelector,
 };
 
 mod action;
@@ -54,20 +56,55 @@ impl ServerManager {
         self.servers.values().any(|server| server.node == name)
     }
 
-    pub fn find_fallback_server(&self, ignore: &Uuid) -> Option<&Server> {
+    pub fn servers_on(&self, name: &str) -> impl Iterator<Item = &Server> {
+        self.servers
+            .values()
+            .filter(move |server| server.node == name)
+    }
+
+    // True while the plugin of the node is in the middle of starting, restarting or stopping a server
+    pub fn is_node_busy(&self, name: &str) -> bool {
+        let on_node = |uuid: &Uuid| {
+            self.servers
+                .get(uuid)
+                .is_some_and(|server| server.node == name)
+        };
+        self.start_requests
+            .iter()
+            .any(|request| match request.stage {
+                StartStage::Queued => false,
+                StartStage::Allocating(index, _) => request.nodes[index] == name,
+                StartStage::Creating(_) => on_node(request.id.uuid()),
+            })
+            || self.restart_requests.iter().any(|request| {
+                matches!(request.stage, RestartStage::Running(_)) && on_node(request.server.uuid())
+            })
+            || self.stop_requests.iter().any(|request| {
+                !matches!(request.stage, StopStage::Queued) && on_node(request.server.uuid())
+            })
+    }
+
+    pub fn find_fallback_server(&self, ignore: &[Uuid], slots: u32) -> Option<&Server> {
         self.servers
             .values()
             .filter(|server| {
-                server.id.uuid() != ignore
-                    && server.ready
-                    && server.state == State::Running
+                !ignore.contains(server.id.uuid())
+                    && server.has_free_slots(slots)
                     && server.allocation.specification.fallback.enabled
             })
-            .max_by_key(|server| server.allocation.specification.fallback.priority)
+            .max_by_key(|server| {
+                (
+                    server.allocation.specification.fallback.priority,
+                    Reverse(server.connected_users + server.reserved),
+                )
+            })
     }
 
-    pub fn get_servers(&self) -> Vec<&Server> {
-        self.servers.values().collect()
+    pub fn select_servers(&self, selector: &ServerSelector) -> Vec<&Server> {
+        self.servers
+            .values()
+            .filter(|server| selector.matches(server))
+            .collect()
     }
 
     pub fn get_server_from_name(&self, name: &str) -> Option<&Server> {
@@ -102,7 +139,7 @@ impl ServerManager {
         }
         self.start_requests.push(request);
     }
-    pub fn _schedule_restart(&mut self, request: RestartRequest) {
+    pub fn schedule_restart(&mut self, request: RestartRequest) {
         if self.restart_requests.contains(&request) {
             return;
         }
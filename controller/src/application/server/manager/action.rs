@@ -70,7 +70,11 @@ impl ServerManager {
                         specification: request.specification.clone(),
                     },
                     connected_users: 0,
-                    token: shared.auth.register_server(request.id.uuid).await,
+                    reserved: 0,
+                    token: shared
+                        .auth
+                        .register_server(request.id.uuid, request.specification.channels().clone())
+                        .await,
                     heart: Heart::new(*config.startup_timeout(), *config.heartbeat_timeout()),
                     state: State::Starting,
                     flags: Flags::default(),
@@ -224,7 +228,7 @@ impl ServerManager {
             }
             shared.auth.unregister(&server.token).await;
 
-            users.remove_users_on_server(server.id.uuid());
+            users.remove_users_on_server(&server, shared).await;
 
             // Remove the screen from the shared screen manager
             shared
@@ -98,11 +98,24 @@ impl ServerManager {
             }
             StartStage::Creating(handle) => {
                 if handle.is_finished() {
+                    // Alert rules are taken from the group when the server starts
+                    let alerts = request
+                        .group
+                        .as_ref()
+                        .and_then(|group| groups.get_group(group))
+                        .map_or_else(Vec::new, |group| group.alerts().clone());
+
                     // Register the screen with the shared screen manager
-                    shared
-                        .screens
-                        .register_screen(request.id.uuid(), handle.await??)
-                        .await;
+                    let mut screen = handle.await??;
+                    if let Some(server) = servers.get(request.id.uuid()) {
+                        shared.screens.register_screen(server, alerts, screen).await;
+                    } else {
+                        warn!(
+                            "Server {} disappeared before its screen could be registered",
+                            request.id
+                        );
+                        screen.cleanup().await?;
+                    }
                     debug!("Server {} has been started", request.id);
                     return Ok(false);
                 }
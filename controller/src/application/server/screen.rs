@@ -2,7 +2,10 @@ use anyhow::Result;
 use tokio::task::JoinHandle;
 use tonic::{Status, async_trait};
 
+pub mod alert;
+pub mod archive;
 pub mod manager;
+pub mod subscription;
 
 pub type BoxedScreen = Box<dyn GenericScreen + Send + Sync>;
 pub type ScreenPullJoinHandle = JoinHandle<Result<Vec<String>, ScreenError>>;
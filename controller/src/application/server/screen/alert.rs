@@ -0,0 +1,172 @@
+use std::collections::{HashMap, VecDeque};
+
+use regex::Regex;
+use simplelog::{info, warn};
+use tokio::time::Instant;
+
+use crate::{
+    application::{
+        group::alert::{AlertAction, AlertRule},
+        server::NameAndUuid,
+    },
+    network::manage::AlertMsg,
+};
+
+// The alert rules of a group compiled for one screen
+#[derive(Default)]
+pub struct ScreenAlerts(Vec<ActiveRule>);
+
+struct ActiveRule {
+    rule: AlertRule,
+    regex: Regex,
+    count: u64,
+    triggered: Option<Instant>,
+}
+
+// Result of checking a batch of lines
+#[derive(Default)]
+pub struct AlertReport {
+    pub alerts: Vec<AlertMsg>,
+    pub restart: bool,
+}
+
+// Counters and restart budgets of the rules of every group, they outlive the screens of the servers
+#[derive(Default)]
+pub struct AlertStats(HashMap<String, HashMap<String, RuleStats>>);
+
+#[derive(Default)]
+struct RuleStats {
+    matches: u64,
+    restarts: VecDeque<Instant>,
+}
+
+pub struct AlertCounter {
+    pub rule: String,
+    pub matches: u64,
+    pub restarts: u32,
+}
+
+impl AlertStats {
+    pub fn counters(&self, group: &str) -> Vec<AlertCounter> {
+        let Some(rules) = self.0.get(group) else {
+            return vec![];
+        };
+        let mut counters = rules
+            .iter()
+            .map(|(rule, stats)| AlertCounter {
+                rule: rule.clone(),
+                matches: stats.matches,
+                restarts: u32::try_from(stats.restarts.len()).unwrap_or(u32::MAX),
+            })
+            .collect::<Vec<_>>();
+        counters.sort_by(|a, b| a.rule.cmp(&b.rule));
+        counters
+    }
+
+    fn rule(&mut self, group: Option<&String>, rule: &str) -> &mut RuleStats {
+        self.0
+            .entry(group.cloned().unwrap_or_default())
+            .or_default()
+            .entry(rule.to_string())
+            .or_default()
+    }
+}
+
+impl RuleStats {
+    // Returns false once the rule used up its restarts for the current window
+    fn try_restart(&mut self, rule: &AlertRule) -> bool {
+        while self
+            .restarts
+            .front()
+            .is_some_and(|time| time.elapsed() >= *rule.restart_window())
+        {
+            self.restarts.pop_front();
+        }
+        if self.restarts.len() >= *rule.max_restarts() as usize {
+            return false;
+        }
+        self.restarts.push_back(Instant::now());
+        true
+    }
+}
+
+impl ScreenAlerts {
+    pub fn new(rules: Vec<AlertRule>) -> Self {
+        Self(
+            rules
+                .into_iter()
+                .filter_map(|rule| match Regex::new(rule.pattern()) {
+                    Ok(regex) => Some(ActiveRule {
+                        rule,
+                        regex,
+                        count: 0,
+                        triggered: None,
+                    }),
+                    Err(error) => {
+                        warn!(
+                            "Alert rule {} has an invalid pattern: {}",
+                            rule.name(),
+                            error
+                        );
+                        None
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn check(
+        &mut self,
+        stats: &mut AlertStats,
+        server: &NameAndUuid,
+        group: Option<&String>,
+        lines: &[String],
+    ) -> AlertReport {
+        let mut report = AlertReport::default();
+        for rule in &mut self.0 {
+            let stats = stats.rule(group, rule.rule.name());
+            for line in lines.iter().filter(|line| rule.regex.is_match(line)) {
+                rule.count += 1;
+                stats.matches += 1;
+                if rule
+                    .triggered
+                    .is_some_and(|time| time.elapsed() < *rule.rule.cooldown())
+                {
+                    continue;
+                }
+                rule.triggered = Some(Instant::now());
+
+                let mut restart = *rule.rule.action() == AlertAction::Restart;
+                if restart && !stats.try_restart(&rule.rule) {
+                    warn!(
+                        "Alert {} reached its restart limit, not restarting server {}",
+                        rule.rule.name(),
+                        server
+                    );
+                    restart = false;
+                }
+                info!(
+                    "Alert {} matched on server {}: {}",
+                    rule.rule.name(),
+                    server,
+                    line
+                );
+                report.restart |= restart;
+                report.alerts.push(AlertMsg {
+                    server: server.uuid().to_string(),
+                    name: server.name().clone(),
+                    group: group.cloned(),
+                    rule: rule.rule.name().clone(),
+                    line: line.clone(),
+                    count: rule.count,
+                    restarting: restart,
+                });
+            }
+        }
+        report
+    }
+}
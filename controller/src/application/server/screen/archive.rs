@@ -0,0 +1,308 @@
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::Result;
+use simplelog::{debug, warn};
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+use uuid::Uuid;
+use zstd::stream::{read::Decoder, write::Encoder};
+
+use crate::{application::server::NameAndUuid, storage::Storage};
+
+// Uncompressed size after which a new segment is started
+const SEGMENT_SIZE: usize = 4 * 1024 * 1024;
+const SEGMENT_EXTENSION: &str = ".log.zst";
+const COMPRESSION_LEVEL: i32 = 3;
+
+// Writes every line of a screen into compressed and rotated files so they outlive the server
+// The archive uses blocking io because the encoder only supports std writers
+pub struct LogArchive {
+    directory: PathBuf,
+    segment: u32,
+    written: usize,
+    encoder: Option<Encoder<'static, File>>,
+}
+
+pub struct ArchivedLogs {
+    pub server: NameAndUuid,
+    pub lines: Vec<String>,
+}
+
+// Owns the archives of all screens on its own thread so screen ticks never wait for the disk
+#[derive(Clone)]
+pub struct ArchiveWriter(UnboundedSender<ArchiveCommand>);
+
+enum ArchiveCommand {
+    Open(NameAndUuid),
+    Append(Uuid, Vec<String>),
+    Close(Uuid),
+}
+
+impl ArchiveWriter {
+    pub fn spawn() -> Result<Self> {
+        let (sender, receiver) = unbounded_channel();
+        thread::Builder::new()
+            .name("log-archive".to_string())
+            .spawn(move || Self::run(receiver))?;
+        Ok(Self(sender))
+    }
+
+    // The receiver lives as long as one of the writers
+    pub fn open(&self, server: &NameAndUuid) {
+        let _ = self.0.send(ArchiveCommand::Open(server.clone()));
+    }
+
+    pub fn append(&self, server: &Uuid, lines: Vec<String>) {
+        let _ = self.0.send(ArchiveCommand::Append(*server, lines));
+    }
+
+    pub fn close(&self, server: &Uuid) {
+        let _ = self.0.send(ArchiveCommand::Close(*server));
+    }
+
+    fn run(mut receiver: UnboundedReceiver<ArchiveCommand>) {
+        let mut archives = HashMap::new();
+        while let Some(command) = receiver.blocking_recv() {
+            match command {
+                ArchiveCommand::Open(server) => match LogArchive::create(&server) {
+                    Ok(archive) => {
+                        archives.insert(*server.uuid(), archive);
+                    }
+                    Err(error) => {
+                        warn!(
+                            "Failed to create log archive for server {}: {}",
+                            server, error
+                        );
+                    }
+                },
+                ArchiveCommand::Append(server, lines) => {
+                    if let Some(archive) = archives.get_mut(&server)
+                        && let Err(error) = archive.append(&lines)
+                    {
+                        warn!(
+                            "Failed to archive screen lines, disabling the archive: {}",
+                            error
+                        );
+                        archives.remove(&server);
+                    }
+                }
+                ArchiveCommand::Close(server) => {
+                    // Dropping the archive finishes the last segment
+                    archives.remove(&server);
+                }
+            }
+        }
+        debug!("All log archive writers dropped, stopping the archive thread");
+    }
+}
+
+impl LogArchive {
+    pub fn create(server: &NameAndUuid) -> Result<Self> {
+        let directory = Storage::server_log_directory(server.name(), server.uuid());
+        fs::create_dir_all(&directory)?;
+
+        // A restarted server keeps its uuid, continue after the existing segments
+        let segment = Self::segments(&directory)?
+            .last()
+            .map_or(0, |(index, _)| index + 1);
+
+        Ok(Self {
+            directory,
+            segment,
+            written: 0,
+            encoder: None,
+        })
+    }
+
+    pub fn append(&mut self, lines: &[String]) -> Result<()> {
+        if self.written >= SEGMENT_SIZE {
+            self.finish()?;
+            self.segment += 1;
+            self.written = 0;
+        }
+
+        let encoder = if let Some(encoder) = &mut self.encoder {
+            encoder
+        } else {
+            // The directory might have been pruned while the server was quiet
+            fs::create_dir_all(&self.directory)?;
+            let file = File::create(
+                self.directory
+                    .join(format!("{}{SEGMENT_EXTENSION}", self.segment)),
+            )?;
+            self.encoder.insert(Encoder::new(file, COMPRESSION_LEVEL)?)
+        };
+        for line in lines {
+            encoder.write_all(line.as_bytes())?;
+            encoder.write_all(b"\n")?;
+            self.written += line.len() + 1;
+        }
+        // Flush every batch so a controller crash does not lose the lines
+        encoder.flush()?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        if let Some(encoder) = self.encoder.take() {
+            encoder.finish()?;
+        }
+        Ok(())
+    }
+
+    // Accepts the uuid of a server or its name. Names resolve to the latest server with that name
+    pub fn read(server: &str, limit: usize) -> Result<Option<ArchivedLogs>> {
+        let Some((server, directory)) = Self::find(server)? else {
+            return Ok(None);
+        };
+
+        // Start at the newest segment and only decompress as many as the limit needs
+        let mut segments = vec![];
+        let mut count = 0;
+        for (_, path) in Self::segments(&directory)?.into_iter().rev() {
+            if count >= limit {
+                break;
+            }
+            let mut data = vec![];
+            // The last segment is not finished if the server or controller did not stop cleanly
+            if let Err(error) = Decoder::new(File::open(&path)?)?.read_to_end(&mut data) {
+                debug!("Log segment {:?} ends early: {}", path, error);
+            }
+            let segment = String::from_utf8_lossy(&data)
+                .lines()
+                .map(str::to_string)
+                .collect::<Vec<_>>();
+            count += segment.len();
+            segments.push(segment);
+        }
+        let mut lines = segments.into_iter().rev().flatten().collect::<Vec<_>>();
+        if lines.len() > limit {
+            lines.drain(..lines.len() - limit);
+        }
+
+        Ok(Some(ArchivedLogs { server, lines }))
+    }
+
+    pub fn prune(retention: &Duration) -> Result<()> {
+        let directory = Storage::server_logs_directory();
+        if !directory.exists() {
+            return Ok(());
+        }
+
+        for name in fs::read_dir(&directory)? {
+            let name = name?.path();
+            if !name.is_dir() {
+                continue;
+            }
+            for server in fs::read_dir(&name)? {
+                let server = server?.path();
+                for (_, path) in Self::segments(&server)? {
+                    if Self::modified(&path).elapsed().unwrap_or_default() >= *retention {
+                        fs::remove_file(&path)?;
+                    }
+                }
+                if fs::read_dir(&server)?.next().is_none() {
+                    fs::remove_dir(&server)?;
+                }
+            }
+            if fs::read_dir(&name)?.next().is_none() {
+                fs::remove_dir(&name)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn find(server: &str) -> Result<Option<(NameAndUuid, PathBuf)>> {
+        if !Storage::is_valid_name(server) {
+            return Ok(None);
+        }
+
+        let directory = Storage::server_logs_directory();
+        if !directory.exists() {
+            return Ok(None);
+        }
+
+        if let Ok(uuid) = Uuid::from_str(server) {
+            for name in fs::read_dir(&directory)? {
+                let path = name?.path().join(uuid.to_string());
+                if path.is_dir()
+                    && let Some(name) = path
+                        .parent()
+                        .and_then(Path::file_name)
+                        .map(|name| name.to_string_lossy().to_string())
+                {
+                    return Ok(Some((NameAndUuid::new(name, uuid), path)));
+                }
+            }
+            return Ok(None);
+        }
+
+        let directory = directory.join(server);
+        if !directory.is_dir() {
+            return Ok(None);
+        }
+        let mut latest = None;
+        for entry in fs::read_dir(&directory)? {
+            let path = entry?.path();
+            let Some(uuid) = path
+                .file_name()
+                .and_then(|name| Uuid::from_str(&name.to_string_lossy()).ok())
+            else {
+                continue;
+            };
+            let modified = Self::segments(&path)?
+                .iter()
+                .map(|(_, path)| Self::modified(path))
+                .max()
+                .unwrap_or(SystemTime::UNIX_EPOCH);
+            if latest.as_ref().is_none_or(|(time, _, _)| modified > *time) {
+                latest = Some((modified, uuid, path));
+            }
+        }
+        Ok(latest.map(|(_, uuid, path)| (NameAndUuid::new(server.to_string(), uuid), path)))
+    }
+
+    // Returns the segments of a server sorted by their index
+    fn segments(directory: &Path) -> Result<Vec<(u32, PathBuf)>> {
+        let mut segments = vec![];
+        for entry in fs::read_dir(directory)? {
+            let path = entry?.path();
+            match path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(SEGMENT_EXTENSION))
+                .map(u32::from_str)
+            {
+                Some(Ok(index)) => segments.push((index, path)),
+                _ => {
+                    warn!("Unexpected file {:?} in log archive", path);
+                }
+            }
+        }
+        segments.sort_by_key(|(index, _)| *index);
+        Ok(segments)
+    }
+
+    fn modified(path: &Path) -> SystemTime {
+        fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .unwrap_or(SystemTime::UNIX_EPOCH)
+    }
+}
+
+impl Drop for LogArchive {
+    fn drop(&mut self) {
+        if let Err(error) = self.finish() {
+            warn!(
+                "Failed to finish log archive {:?}: {}",
+                self.directory, error
+            );
+        }
+    }
+}
@@ -1,11 +1,17 @@
-use std::{collections::HashMap, time::Duration};
+use std::{collections::HashMap, mem::replace, time::Duration};
 
 use anyhow::Result;
 use common::cache::FixedSizeCache;
 use futures::FutureExt;
 use simplelog::warn;
+#[cfg(feature = "wasm-plugins")]
+use tokio::sync::{
+    Mutex,
+    mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel},
+};
 use tokio::{
     sync::RwLock,
+    task::spawn_blocking,
     time::{Interval, MissedTickBehavior, interval},
 };
 use tokio_stream::wrappers::ReceiverStream;
@@ -13,29 +19,87 @@ use tonic::Status;
 use uuid::Uuid;
 
 use crate::{
-    application::{TICK_RATE, subscriber::Subscriber},
-    network::manage::ScreenLines,
+    application::{
+        TICK_RATE,
+        group::alert::AlertRule,
+        server::{NameAndUuid, Server},
+        subscriber::{Subscriber, manager::SubscriberManager, watcher::Watcher},
+    },
+    config::Config,
+    network::manage::{AlertMsg, ScreenLines},
+    storage::Storage,
 };
 
-use super::{BoxedScreen, ScreenPullJoinHandle, ScreenWriteJoinHandle};
+use super::{
+    BoxedScreen, ScreenPullJoinHandle, ScreenWriteJoinHandle,
+    alert::{AlertCounter, AlertStats, ScreenAlerts},
+    archive::{ArchiveWriter, ArchivedLogs, LogArchive},
+    subscription::{CachedLine, ScreenStart, ScreenStream, ScreenSubscriber, ScreenSubscription},
+};
 
 const SCREEN_TICK_RATE: u64 = TICK_RATE / 3;
 const PASSIVE_SCREEN_TICK_RATE_MULTIPLIER: u64 = 250;
 
-type SubscriberHolder<B> = Vec<Subscriber<B>>;
+// Upper limit for the scrollback a subscriber can request
+const MAX_SCROLLBACK: usize = 1024;
 
 pub struct ScreenManager {
     screens: RwLock<HashMap<Uuid, ActiveScreen>>,
+    streams: RwLock<Vec<ScreenStream>>,
+
+    /* Push */
+    // Kept outside of the screen lock because plugins notify while the controller may wait on them
+    #[cfg(feature = "wasm-plugins")]
+    notifier: UnboundedSender<Uuid>,
+    #[cfg(feature = "wasm-plugins")]
+    notifications: Mutex<UnboundedReceiver<Uuid>>,
+
+    /* Alerts */
+    alerts: RwLock<AlertStats>,
+
+    /* Archive */
+    retention: Option<Duration>,
+    writer: Option<ArchiveWriter>,
 }
 
 impl ScreenManager {
-    pub fn init() -> Self {
+    pub fn init(config: &Config) -> Self {
+        let retention = config.archive_logs().then(|| *config.log_retention());
+        if let Some(retention) = &retention
+            && let Err(error) = LogArchive::prune(retention)
+        {
+            warn!("Failed to remove old server logs: {}", error);
+        }
+
+        let writer = retention.and_then(|_| match ArchiveWriter::spawn() {
+            Ok(writer) => Some(writer),
+            Err(error) => {
+                warn!("Failed to start the log archive writer: {}", error);
+                None
+            }
+        });
+
+        #[cfg(feature = "wasm-plugins")]
+        let (notifier, notifications) = unbounded_channel();
         Self {
             screens: RwLock::new(HashMap::new()),
+            streams: RwLock::new(vec![]),
+            #[cfg(feature = "wasm-plugins")]
+            notifier,
+            #[cfg(feature = "wasm-plugins")]
+            notifications: Mutex::new(notifications),
+            alerts: RwLock::new(AlertStats::default()),
+            retention,
+            writer,
         }
     }
 
-    pub async fn register_screen(&self, server: &Uuid, mut screen: BoxedScreen) {
+    pub async fn register_screen(
+        &self,
+        server: &Server,
+        alerts: Vec<AlertRule>,
+        mut screen: BoxedScreen,
+    ) {
         if !screen.is_supported() {
             if let Err(error) = screen.cleanup().await {
                 warn!("Failed to cleanup unsupported screen: {}", error);
@@ -43,10 +107,31 @@ impl ScreenManager {
             return;
         }
 
+        let archive = self.writer.clone();
+        if let Some(archive) = &archive {
+            archive.open(server.id());
+        }
+
+        let mut screen = ActiveScreen::new(
+            server.id().clone(),
+            server.group().clone(),
+            ScreenAlerts::new(alerts),
+            screen,
+            archive,
+        );
+        // Add the screen to the streams that follow servers like this one
+        for stream in self.streams.read().await.iter() {
+            if stream.is_alive() && stream.matches(server) {
+                screen
+                    .subscribers
+                    .push(stream.create_subscriber(server.id()));
+            }
+        }
+
         self.screens
             .write()
             .await
-            .insert(*server, ActiveScreen::new(screen));
+            .insert(*server.id().uuid(), screen);
     }
 
     pub async fn unregister_screen(&self, server: &Uuid) -> Result<()> {
@@ -56,9 +141,63 @@ impl ScreenManager {
             drop(screen); // Drop the screen
         }
 
+        if let Some(retention) = self.retention
+            && let Err(error) = spawn_blocking(move || LogArchive::prune(&retention)).await?
+        {
+            warn!("Failed to remove old server logs: {}", error);
+        }
         Ok(())
     }
 
+    // Used after the plugin was reloaded. Returns the screens the caller has to clean up together with the old plugin
+    pub async fn replace_screen(&self, server: &Uuid, screen: BoxedScreen) -> Vec<BoxedScreen> {
+        let mut screens = self.screens.write().await;
+        if screen.is_supported() {
+            return match screens.get_mut(server) {
+                Some(active) => vec![active.replace(screen)],
+                // The old instance did not provide a screen, so there is nothing to follow
+                None => vec![screen],
+            };
+        }
+
+        match screens.remove(server) {
+            Some(mut active) => {
+                active.close().await;
+                vec![active.screen, screen]
+            }
+            None => vec![screen],
+        }
+    }
+
+    pub async fn read_logs(&self, server: String, limit: usize) -> Result<ArchivedLogs, Status> {
+        if self.retention.is_none() {
+            return Err(Status::failed_precondition(
+                "Log archiving is disabled on this controller",
+            ));
+        }
+        if !Storage::is_valid_name(&server) {
+            return Err(Status::invalid_argument("Invalid server name"));
+        }
+
+        match spawn_blocking(move || LogArchive::read(&server, limit)).await {
+            Ok(Ok(Some(logs))) => Ok(logs),
+            Ok(Ok(None)) => Err(Status::not_found("No logs archived for this server")),
+            Ok(Err(error)) => Err(Status::internal(error.to_string())),
+            Err(error) => Err(Status::internal(error.to_string())),
+        }
+    }
+
+    pub async fn alert_counters(&self, group: &str) -> Vec<AlertCounter> {
+        self.alerts.read().await.counters(group)
+    }
+
+    // Called by screens that have new output
+    #[cfg(feature = "wasm-plugins")]
+    pub fn notify(&self, server: Uuid) {
+        // The receiver lives as long as the manager
+        let _ = self.notifier.send(server);
+    }
+
     pub async fn write(&self, server: &Uuid, data: &[u8]) -> Result<ScreenWriteJoinHandle, Status> {
         let screens = self.screens.read().await;
         let screen = screens.get(server).ok_or(Status::unimplemented(
@@ -67,9 +206,43 @@ impl ScreenManager {
         Ok(screen.write(data))
     }
 
+    // Starts all writes before waiting for any of them
+    pub async fn write_many(
+        &self,
+        servers: Vec<NameAndUuid>,
+        data: &[u8],
+    ) -> Vec<(NameAndUuid, Result<(), Status>)> {
+        let handles = {
+            let screens = self.screens.read().await;
+            servers
+                .into_iter()
+                .map(|server| {
+                    let handle = screens.get(server.uuid()).map(|screen| screen.write(data));
+                    (server, handle)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut results = Vec::with_capacity(handles.len());
+        for (server, handle) in handles {
+            let result = match handle {
+                Some(handle) => match handle.await {
+                    Ok(result) => result.map_err(Into::into),
+                    Err(error) => Err(Status::internal(error.to_string())),
+                },
+                None => Err(Status::unimplemented(
+                    "The plugin that handles this screen does not support it",
+                )),
+            };
+            results.push((server, result));
+        }
+        results
+    }
+
     pub async fn subscribe_screen(
         &self,
         server: &Uuid,
+        subscription: ScreenSubscription,
     ) -> Result<ReceiverStream<Result<ScreenLines, Status>>, Status> {
         let mut screens = self.screens.write().await;
         let screen = screens.get_mut(server).ok_or(Status::unimplemented(
@@ -77,18 +250,52 @@ impl ScreenManager {
         ))?;
 
         let (subscriber, receiver) = Subscriber::create_network();
-        screen.push(subscriber).await;
+        let (start, subscriber) = subscription.into_subscriber(subscriber);
+        screen.push(&start, subscriber).await;
         Ok(receiver)
     }
+
+    // The current servers are selected by the caller because only the controller knows them
+    pub async fn subscribe_screens(&self, servers: &[NameAndUuid], stream: ScreenStream) {
+        let start = stream.start();
+        let mut screens = self.screens.write().await;
+        for server in servers {
+            if let Some(screen) = screens.get_mut(server.uuid()) {
+                screen.push(&start, stream.create_subscriber(server)).await;
+            }
+        }
+        self.streams.write().await.push(stream);
+    }
 }
 
 // Ticking
 impl ScreenManager {
-    pub async fn tick(&self) -> Result<()> {
-        for screen in self.screens.write().await.values_mut() {
-            screen.tick().await?;
+    // Returns the servers that an alert rule wants restarted
+    pub async fn tick(&self, subscribers: &SubscriberManager) -> Result<Vec<NameAndUuid>> {
+        self.streams.write().await.retain(ScreenStream::is_alive);
+
+        let mut screens = self.screens.write().await;
+        #[cfg(feature = "wasm-plugins")]
+        {
+            let mut notifications = self.notifications.lock().await;
+            while let Ok(server) = notifications.try_recv() {
+                if let Some(screen) = screens.get_mut(&server) {
+                    screen.notify();
+                }
+            }
         }
-        Ok(())
+
+        let mut stats = self.alerts.write().await;
+        let mut restarts = vec![];
+        for screen in screens.values_mut() {
+            if screen
+                .tick(&mut stats, subscribers.network().alert())
+                .await?
+            {
+                restarts.push(screen.server.clone());
+            }
+        }
+        Ok(restarts)
     }
 
     pub async fn cleanup(&self) -> Result<()> {
@@ -102,16 +309,32 @@ impl ScreenManager {
 }
 
 struct ActiveScreen {
+    server: NameAndUuid,
+    group: Option<String>,
     // First is for normal ticks and second is for passiv ticks to prevent buffers from overflowing
     intervals: (Interval, Interval),
     screen: BoxedScreen,
     handle: Option<ScreenPullJoinHandle>,
-    subscribers: SubscriberHolder<ScreenLines>,
-    cache: FixedSizeCache<String>,
+    subscribers: Vec<ScreenSubscriber>,
+    cache: FixedSizeCache<CachedLine>,
+    sequence: u64,
+    archive: Option<ArchiveWriter>,
+    alerts: ScreenAlerts,
+
+    /* Push */
+    // Set once the screen announced output itself, from then on it is only pulled when it does
+    pushing: bool,
+    pending: bool,
 }
 
 impl ActiveScreen {
-    pub fn new(screen: BoxedScreen) -> Self {
+    pub fn new(
+        server: NameAndUuid,
+        group: Option<String>,
+        alerts: ScreenAlerts,
+        screen: BoxedScreen,
+        archive: Option<ArchiveWriter>,
+    ) -> Self {
         let mut intervals = (
             interval(Duration::from_millis(1000 / SCREEN_TICK_RATE)),
             interval(Duration::from_millis(
@@ -125,11 +348,18 @@ impl ActiveScreen {
             .1
             .set_missed_tick_behavior(MissedTickBehavior::Skip);
         Self {
+            server,
+            group,
             intervals,
             screen,
             handle: None,
             subscribers: vec![],
-            cache: FixedSizeCache::new(120),
+            cache: FixedSizeCache::new(MAX_SCROLLBACK),
+            sequence: 0,
+            archive,
+            alerts,
+            pushing: false,
+            pending: false,
         }
     }
 
@@ -137,14 +367,15 @@ impl ActiveScreen {
         self.screen.write(data)
     }
 
-    pub async fn push(&mut self, subscriber: Subscriber<ScreenLines>) {
-        if self.cache.has_data()
-            && !subscriber
-                .send_message(ScreenLines {
-                    lines: self.cache.clone_items(),
-                })
-                .await
-        {
+    #[cfg(feature = "wasm-plugins")]
+    pub fn notify(&mut self) {
+        self.pushing = true;
+        self.pending = true;
+    }
+
+    pub async fn push(&mut self, start: &ScreenStart, subscriber: ScreenSubscriber) {
+        let replay = start.replay(self.cache.clone_items());
+        if !subscriber.send(&replay).await {
             warn!("Failed to send initial screen data to subscriber!");
             return;
         }
@@ -152,49 +383,129 @@ impl ActiveScreen {
         self.subscribers.push(subscriber);
     }
 
-    pub async fn tick(&mut self) -> Result<()> {
-        if self.intervals.0.tick().now_or_never().is_none() {
+    // Returns true if an alert rule wants the server restarted
+    pub async fn tick(
+        &mut self,
+        stats: &mut AlertStats,
+        alerts: &Watcher<(), AlertMsg>,
+    ) -> Result<bool> {
+        // Output that was announced is fetched right away
+        if self.intervals.0.tick().now_or_never().is_none() && !self.pending {
             // Skip tick
-            return Ok(());
+            return Ok(false);
         }
 
         // Remove all dead subscribers
-        self.subscribers.retain(Subscriber::is_alive);
+        self.subscribers.retain(ScreenSubscriber::is_alive);
 
-        if self.intervals.1.tick().now_or_never().is_none() && self.subscribers.is_empty() {
-            // If no one is watching dont pull and no passiv tick is needed
-            return Ok(());
+        let passive = self.intervals.1.tick().now_or_never().is_some();
+        if !passive
+            && self.subscribers.is_empty()
+            && self.archive.is_none()
+            && self.alerts.is_empty()
+        {
+            // If no one is watching, archiving or alerting dont pull and no passiv tick is needed
+            return Ok(false);
         }
 
+        let mut restart = false;
         self.handle = match self.handle.take() {
             Some(handle) if handle.is_finished() => {
                 let lines = handle.await?.map_err(Into::<Status>::into);
                 match lines {
                     Ok(lines) => {
                         if !lines.is_empty() {
+                            self.archive_lines(&lines);
+                            restart = self.check_alerts(stats, alerts, &lines).await;
+                            let lines = lines
+                                .into_iter()
+                                .map(|line| {
+                                    self.sequence += 1;
+                                    CachedLine::new(self.sequence, line)
+                                })
+                                .collect::<Vec<_>>();
                             self.cache.extend(lines.clone());
-                            let lines = ScreenLines { lines };
                             for subscriber in &self.subscribers {
-                                subscriber.send_network(Ok(lines.clone())).await;
+                                subscriber.send(&lines).await;
                             }
                         }
                     }
                     Err(error) => {
                         for subscriber in &self.subscribers {
-                            subscriber.send_network(Err(error.clone())).await;
+                            subscriber.send_error(error.clone()).await;
                         }
                     }
                 }
-                Some(self.screen.pull())
+                self.next_pull(passive)
             }
             Some(handle) => Some(handle),
-            None => Some(self.screen.pull()),
+            None => self.next_pull(passive),
         };
 
-        Ok(())
+        Ok(restart)
     }
 
     pub async fn cleanup(&mut self) -> Result<()> {
+        // Keep the last lines the server printed before it stopped
+        if self.archive.is_some()
+            && let Some(handle) = self.handle.take()
+            && handle.is_finished()
+            && let Ok(lines) = handle.await?
+        {
+            self.archive_lines(&lines);
+        }
+        self.close().await;
+
         self.screen.cleanup().await
     }
+
+    async fn close(&mut self) {
+        if let Some(archive) = self.archive.take() {
+            archive.close(self.server.uuid());
+        }
+        for subscriber in &self.subscribers {
+            subscriber.close().await;
+        }
+    }
+
+    // Lines of a pull that is still running on the old screen are lost
+    fn replace(&mut self, screen: BoxedScreen) -> BoxedScreen {
+        self.handle = None;
+        self.pushing = false;
+        self.pending = false;
+        replace(&mut self.screen, screen)
+    }
+
+    fn next_pull(&mut self, passive: bool) -> Option<ScreenPullJoinHandle> {
+        // The passive tick still pulls pushing screens in case a notification got lost
+        if self.pushing && !replace(&mut self.pending, false) && !passive {
+            return None;
+        }
+        Some(self.screen.pull())
+    }
+
+    async fn check_alerts(
+        &mut self,
+        stats: &mut AlertStats,
+        watcher: &Watcher<(), AlertMsg>,
+        lines: &[String],
+    ) -> bool {
+        if self.alerts.is_empty() {
+            return false;
+        }
+
+        let report = self
+            .alerts
+            .check(stats, &self.server, self.group.as_ref(), lines);
+        for alert in report.alerts {
+            watcher.publish(alert).await;
+        }
+        report.restart
+    }
+
+    fn archive_lines(&self, lines: &[String]) {
+        if let Some(archive) = &self.archive {
+            archive.append(self.server.uuid(), lines.to_vec());
+        }
+    }
 }
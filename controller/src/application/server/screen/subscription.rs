@@ -0,0 +1,250 @@
+use std::{
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use regex::Regex;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::Status;
+
+use crate::{
+    application::{
+        server::{NameAndUuid, Server, selector::ServerSelector},
+        subscriber::Subscriber,
+    },
+    network::manage::{ScreenLine, ScreenLineHighlight, ScreenLines, TaggedScreenLines},
+};
+
+pub const DEFAULT_SCROLLBACK: usize = 120;
+
+// Which cached lines are replayed before the live lines
+pub enum ScreenStart {
+    Scrollback(usize),
+    Sequence(u64),
+    Timestamp(u64),
+}
+
+#[derive(Clone)]
+pub struct ScreenFilter {
+    regex: Regex,
+    // Send every line and only mark the matches
+    highlight: bool,
+}
+
+pub struct ScreenSubscription {
+    start: ScreenStart,
+    filter: Option<ScreenFilter>,
+}
+
+pub struct ScreenSubscriber {
+    sink: ScreenSink,
+    filter: Option<ScreenFilter>,
+}
+
+enum ScreenSink {
+    Single(Subscriber<ScreenLines>),
+    // One stream that receives the lines of many screens
+    Tagged(Arc<Subscriber<TaggedScreenLines>>, NameAndUuid),
+}
+
+// A stream over all screens that match the selector, including the ones of servers started later
+pub struct ScreenStream {
+    selector: ServerSelector,
+    scrollback: usize,
+    filter: Option<ScreenFilter>,
+    subscriber: Arc<Subscriber<TaggedScreenLines>>,
+}
+
+#[derive(Clone)]
+pub struct CachedLine {
+    sequence: u64,
+    timestamp: u64,
+    content: String,
+}
+
+impl ScreenFilter {
+    pub fn new(regex: Regex, highlight: bool) -> Self {
+        Self { regex, highlight }
+    }
+
+    fn apply(&self, line: &CachedLine) -> Option<ScreenLine> {
+        let highlights = self
+            .regex
+            .find_iter(&line.content)
+            .map(|found| ScreenLineHighlight {
+                start: u32::try_from(found.start()).unwrap_or(u32::MAX),
+                end: u32::try_from(found.end()).unwrap_or(u32::MAX),
+            })
+            .collect::<Vec<_>>();
+        if highlights.is_empty() && !self.highlight {
+            return None;
+        }
+        Some(line.to_network(highlights))
+    }
+}
+
+impl ScreenStart {
+    // Selects the cached lines this subscription wants to see first
+    pub fn replay(&self, cache: Vec<CachedLine>) -> Vec<CachedLine> {
+        match *self {
+            ScreenStart::Scrollback(amount) => {
+                let skip = cache.len().saturating_sub(amount);
+                cache.into_iter().skip(skip).collect()
+            }
+            ScreenStart::Sequence(sequence) => cache
+                .into_iter()
+                .filter(|line| line.sequence >= sequence)
+                .collect(),
+            ScreenStart::Timestamp(timestamp) => cache
+                .into_iter()
+                .filter(|line| line.timestamp >= timestamp)
+                .collect(),
+        }
+    }
+}
+
+impl ScreenSubscription {
+    pub fn new(start: ScreenStart, filter: Option<ScreenFilter>) -> Self {
+        Self { start, filter }
+    }
+
+    pub fn into_subscriber(
+        self,
+        subscriber: Subscriber<ScreenLines>,
+    ) -> (ScreenStart, ScreenSubscriber) {
+        (
+            self.start,
+            ScreenSubscriber {
+                sink: ScreenSink::Single(subscriber),
+                filter: self.filter,
+            },
+        )
+    }
+}
+
+impl ScreenStream {
+    pub fn new(
+        selector: ServerSelector,
+        scrollback: usize,
+        filter: Option<ScreenFilter>,
+    ) -> (Self, ReceiverStream<Result<TaggedScreenLines, Status>>) {
+        let (subscriber, receiver) = Subscriber::create_network();
+        (
+            Self {
+                selector: selector.into_identity(),
+                scrollback,
+                filter,
+                subscriber: Arc::new(subscriber),
+            },
+            receiver,
+        )
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.subscriber.is_alive()
+    }
+
+    pub fn selector(&self) -> &ServerSelector {
+        &self.selector
+    }
+
+    pub fn matches(&self, server: &Server) -> bool {
+        self.selector.matches(server)
+    }
+
+    pub fn start(&self) -> ScreenStart {
+        ScreenStart::Scrollback(self.scrollback)
+    }
+
+    pub fn create_subscriber(&self, server: &NameAndUuid) -> ScreenSubscriber {
+        ScreenSubscriber {
+            sink: ScreenSink::Tagged(self.subscriber.clone(), server.clone()),
+            filter: self.filter.clone(),
+        }
+    }
+}
+
+impl ScreenSubscriber {
+    pub fn is_alive(&self) -> bool {
+        match &self.sink {
+            ScreenSink::Single(subscriber) => subscriber.is_alive(),
+            ScreenSink::Tagged(subscriber, _) => subscriber.is_alive(),
+        }
+    }
+
+    // Returns false if the subscriber is gone
+    pub async fn send(&self, lines: &[CachedLine]) -> bool {
+        let Some(lines) = self.create_message(lines) else {
+            return true;
+        };
+        match &self.sink {
+            ScreenSink::Single(subscriber) => subscriber.send_message(lines).await,
+            ScreenSink::Tagged(subscriber, server) => {
+                subscriber
+                    .send_message(Self::tag(server, Some(lines), false))
+                    .await
+            }
+        }
+    }
+
+    pub async fn send_error(&self, error: Status) {
+        // One failing screen should not end a stream that also carries other screens
+        if let ScreenSink::Single(subscriber) = &self.sink {
+            subscriber.send_network(Err(error)).await;
+        }
+    }
+
+    // Tells tagged streams that no more lines follow for this screen
+    pub async fn close(&self) {
+        if let ScreenSink::Tagged(subscriber, server) = &self.sink {
+            subscriber.send_message(Self::tag(server, None, true)).await;
+        }
+    }
+
+    fn tag(server: &NameAndUuid, lines: Option<ScreenLines>, closed: bool) -> TaggedScreenLines {
+        TaggedScreenLines {
+            server: server.uuid().to_string(),
+            name: server.name().clone(),
+            lines,
+            closed,
+        }
+    }
+
+    // Returns None if the filter removed every line
+    fn create_message(&self, lines: &[CachedLine]) -> Option<ScreenLines> {
+        let entries: Vec<_> = match &self.filter {
+            Some(filter) => lines.iter().filter_map(|line| filter.apply(line)).collect(),
+            None => lines.iter().map(|line| line.to_network(vec![])).collect(),
+        };
+        if entries.is_empty() {
+            return None;
+        }
+        Some(ScreenLines {
+            lines: entries.iter().map(|entry| entry.content.clone()).collect(),
+            entries,
+        })
+    }
+}
+
+impl CachedLine {
+    pub fn new(sequence: u64, content: String) -> Self {
+        Self {
+            sequence,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| {
+                    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+                }),
+            content,
+        }
+    }
+
+    fn to_network(&self, highlights: Vec<ScreenLineHighlight>) -> ScreenLine {
+        ScreenLine {
+            content: self.content.clone(),
+            sequence: self.sequence,
+            timestamp: self.timestamp,
+            highlights,
+        }
+    }
+}
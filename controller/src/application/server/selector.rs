@@ -0,0 +1,74 @@
+use regex::Regex;
+use uuid::Uuid;
+
+use super::{Server, State};
+
+// Every criteria that is set has to match. An empty selector matches every server
+#[derive(Clone, Default)]
+pub struct ServerSelector {
+    ids: Vec<Uuid>,
+    group: Option<String>,
+    node: Option<String>,
+    name: Option<Regex>,
+    empty: bool,
+    state: Option<State>,
+    ready: Option<bool>,
+}
+
+impl ServerSelector {
+    pub fn new(
+        ids: Vec<Uuid>,
+        group: Option<String>,
+        node: Option<String>,
+        name: Option<Regex>,
+        empty: bool,
+        state: Option<State>,
+        ready: Option<bool>,
+    ) -> Self {
+        Self {
+            ids,
+            group,
+            node,
+            name,
+            empty,
+            state,
+            ready,
+        }
+    }
+
+    pub fn group(name: String) -> Self {
+        Self {
+            group: Some(name),
+            ..Self::default()
+        }
+    }
+
+    // Drops the criteria that change while a server is running
+    pub fn into_identity(self) -> Self {
+        Self {
+            empty: false,
+            state: None,
+            ready: None,
+            ..self
+        }
+    }
+
+    pub fn matches(&self, server: &Server) -> bool {
+        (self.ids.is_empty() || self.ids.contains(server.id().uuid()))
+            && self
+                .group
+                .as_ref()
+                .is_none_or(|group| server.group().as_ref() == Some(group))
+            && self.node.as_ref().is_none_or(|node| server.node() == node)
+            && self
+                .name
+                .as_ref()
+                .is_none_or(|name| name.is_match(server.id().name()))
+            && (!self.empty || *server.connected_users() == 0)
+            && self
+                .state
+                .as_ref()
+                .is_none_or(|state| server.state() == state)
+            && self.ready.is_none_or(|ready| *server.ready() == ready)
+    }
+}
@@ -1,5 +1,11 @@
 use anyhow::Result;
-use tokio::sync::mpsc::{Receiver, Sender, channel};
+use simplelog::warn;
+#[cfg(feature = "wasm-plugins")]
+use tokio::sync::mpsc::Receiver;
+use tokio::sync::mpsc::{
+    Sender, channel,
+    error::TrySendError::{Closed, Full},
+};
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::Status;
 
@@ -7,9 +13,13 @@ pub mod manager;
 pub mod watcher;
 
 const SUBSCRIPTION_BUFFER: usize = 128;
+// Plugins drain their events once per tick, so this has to cover a burst of events between two ticks
+#[cfg(feature = "wasm-plugins")]
+const PLUGIN_EVENT_BUFFER: usize = 1024;
 
 enum Dispatch<T> {
     Network(Sender<Result<T, Status>>),
+    // Plugins only drain their events when they tick, so publishers must never wait for them
     Plugin(Sender<Result<T>>),
 }
 
@@ -24,8 +34,9 @@ impl<T> Subscriber<T> {
         )
     }
 
+    #[cfg(feature = "wasm-plugins")]
     pub fn create_plugin() -> (Self, Receiver<Result<T>>) {
-        let (sender, receiver) = channel(SUBSCRIPTION_BUFFER);
+        let (sender, receiver) = channel(PLUGIN_EVENT_BUFFER);
         (Self(Dispatch::Plugin(sender)), receiver)
     }
 
@@ -53,14 +64,27 @@ impl<T> Subscriber<T> {
                     true
                 }
             }
-            Dispatch::Plugin(sender) => {
-                if sender.send(Ok(message)).await.is_err() {
-                    // Channel closed
-                    false
-                } else {
-                    true
-                }
+            Dispatch::Plugin(sender) => Self::try_send_plugin(sender, message),
+        }
+    }
+
+    // Never waits for the receiver. Fails if the channel is closed or full
+    pub fn try_send_message(&self, message: T) -> bool {
+        match &self.0 {
+            Dispatch::Network(sender) => sender.try_send(Ok(message)).is_ok(),
+            Dispatch::Plugin(sender) => Self::try_send_plugin(sender, message),
+        }
+    }
+
+    // A plugin that does not keep up loses events instead of growing the queue without limit
+    fn try_send_plugin(sender: &Sender<Result<T>>, message: T) -> bool {
+        match sender.try_send(Ok(message)) {
+            Ok(()) => true,
+            Err(Full(_)) => {
+                warn!("Event queue of a plugin is full, dropping event");
+                true
             }
+            Err(Closed(_)) => false,
         }
     }
 
@@ -71,3 +95,33 @@ impl<T> Subscriber<T> {
         }
     }
 }
+
+#[cfg(all(test, feature = "wasm-plugins"))]
+mod tests {
+    use super::{PLUGIN_EVENT_BUFFER, Subscriber};
+
+    #[tokio::test]
+    async fn plugin_queue_drops_events_when_full() {
+        let (subscriber, mut receiver) = Subscriber::create_plugin();
+        for event in 0..PLUGIN_EVENT_BUFFER {
+            assert!(subscriber.try_send_message(event));
+        }
+
+        // The subscriber stays alive, only the events that do not fit are lost
+        assert!(subscriber.send_message(PLUGIN_EVENT_BUFFER).await);
+        assert!(subscriber.try_send_message(PLUGIN_EVENT_BUFFER + 1));
+        assert!(subscriber.is_alive());
+
+        let mut events = vec![];
+        while let Ok(event) = receiver.try_recv() {
+            events.push(event.unwrap());
+        }
+        assert_eq!(events, (0..PLUGIN_EVENT_BUFFER).collect::<Vec<_>>());
+
+        // Space is freed once the plugin drained its queue
+        assert!(subscriber.try_send_message(0));
+        drop(receiver);
+        assert!(!subscriber.try_send_message(0));
+        assert!(!subscriber.is_alive());
+    }
+}
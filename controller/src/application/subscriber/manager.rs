@@ -1,9 +1,17 @@
 use anyhow::Result;
-use event::server::{ServerEvent, ServerReadyEvent};
+use event::{
+    group::{GroupActiveEvent, GroupScaledEvent},
+    node::NodeActiveEvent,
+    server::{ServerEvent, ServerReadyEvent},
+    user::{UserEvent, UserTransferEvent},
+};
 use getset::Getters;
 use uuid::Uuid;
 
-use crate::network::client::{ChannelMsg, PowerMsg, ReadyMsg, TransferMsg};
+use crate::network::{
+    client::{ActionMsg, ChannelMsg, ChannelRequestMsg, PowerMsg, QueueMsg, ReadyMsg, TransferMsg},
+    manage::AlertMsg,
+};
 
 use super::watcher::Watcher;
 
@@ -21,6 +29,24 @@ pub struct PluginEvents {
     /* Ready */
     #[getset(get = "pub")]
     server_change_ready: Watcher<(), ServerReadyEvent>,
+
+    /* Users */
+    #[getset(get = "pub")]
+    user_connected: Watcher<(), UserEvent>,
+    #[getset(get = "pub")]
+    user_disconnected: Watcher<(), UserEvent>,
+    #[getset(get = "pub")]
+    user_transferred: Watcher<(), UserTransferEvent>,
+
+    /* Groups */
+    #[getset(get = "pub")]
+    group_change_active: Watcher<(), GroupActiveEvent>,
+    #[getset(get = "pub")]
+    group_scaled: Watcher<(), GroupScaledEvent>,
+
+    /* Nodes */
+    #[getset(get = "pub")]
+    node_change_active: Watcher<(), NodeActiveEvent>,
 }
 
 #[derive(Getters)]
@@ -29,13 +55,23 @@ pub struct NetworkEvents {
     #[getset(get = "pub")]
     transfer: Watcher<Uuid, TransferMsg>,
     #[getset(get = "pub")]
+    queue: Watcher<Uuid, QueueMsg>,
+    #[getset(get = "pub")]
+    action: Watcher<Uuid, ActionMsg>,
+    #[getset(get = "pub")]
     channel: Watcher<String, ChannelMsg>,
+    #[getset(get = "pub")]
+    request: Watcher<String, ChannelRequestMsg>,
 
     /* Server */
     #[getset(get = "pub")]
     power: Watcher<(), PowerMsg>,
     #[getset(get = "pub")]
     ready: Watcher<(), ReadyMsg>,
+
+    /* Screen */
+    #[getset(get = "pub")]
+    alert: Watcher<(), AlertMsg>,
 }
 
 #[derive(Getters)]
@@ -54,12 +90,22 @@ impl SubscriberManager {
                 server_start: Watcher::new(),
                 server_stop: Watcher::new(),
                 server_change_ready: Watcher::new(),
+                user_connected: Watcher::new(),
+                user_disconnected: Watcher::new(),
+                user_transferred: Watcher::new(),
+                group_change_active: Watcher::new(),
+                group_scaled: Watcher::new(),
+                node_change_active: Watcher::new(),
             },
             network: NetworkEvents {
                 transfer: Watcher::new(),
+                queue: Watcher::new(),
+                action: Watcher::new(),
                 channel: Watcher::new(),
+                request: Watcher::new(),
                 power: Watcher::new(),
                 ready: Watcher::new(),
+                alert: Watcher::new(),
             },
         }
     }
@@ -70,26 +116,46 @@ impl SubscriberManager {
     pub async fn tick(&self) -> Result<()> {
         // Cleanup dead subscribers
         self.network.channel.cleanup().await;
+        self.network.request.cleanup().await;
         self.network.transfer.cleanup().await;
+        self.network.queue.cleanup().await;
+        self.network.action.cleanup().await;
         self.network.power.cleanup().await;
         self.network.ready.cleanup().await;
+        self.network.alert.cleanup().await;
 
         self.plugin.server_start.cleanup().await;
         self.plugin.server_stop.cleanup().await;
         self.plugin.server_change_ready.cleanup().await;
+        self.plugin.user_connected.cleanup().await;
+        self.plugin.user_disconnected.cleanup().await;
+        self.plugin.user_transferred.cleanup().await;
+        self.plugin.group_change_active.cleanup().await;
+        self.plugin.group_scaled.cleanup().await;
+        self.plugin.node_change_active.cleanup().await;
         Ok(())
     }
 
     pub async fn cleanup(&self) -> Result<()> {
         // Drop all sender
         self.network.channel.clear().await;
+        self.network.request.clear().await;
         self.network.transfer.clear().await;
+        self.network.queue.clear().await;
+        self.network.action.clear().await;
         self.network.power.clear().await;
         self.network.ready.clear().await;
+        self.network.alert.clear().await;
 
         self.plugin.server_start.clear().await;
         self.plugin.server_stop.clear().await;
         self.plugin.server_change_ready.clear().await;
+        self.plugin.user_connected.clear().await;
+        self.plugin.user_disconnected.clear().await;
+        self.plugin.user_transferred.clear().await;
+        self.plugin.group_change_active.clear().await;
+        self.plugin.group_scaled.clear().await;
+        self.plugin.node_change_active.clear().await;
         Ok(())
     }
 }
@@ -1 +1,4 @@
+pub mod group;
+pub mod node;
 pub mod server;
+pub mod user;
@@ -0,0 +1,4 @@
+pub type GroupActiveEvent = (String, bool);
+
+// The second value is the new target count of servers
+pub type GroupScaledEvent = (String, u32);
@@ -0,0 +1 @@
+pub type NodeActiveEvent = (String, bool);
@@ -0,0 +1,53 @@
+use getset::Getters;
+
+use crate::application::server::NameAndUuid;
+
+use super::server::ServerEvent;
+
+// Only plugins read user events
+#[derive(Getters, Clone)]
+pub struct UserEvent {
+    #[cfg(feature = "wasm-plugins")]
+    #[getset(get = "pub")]
+    pub user: NameAndUuid,
+    #[cfg(feature = "wasm-plugins")]
+    #[getset(get = "pub")]
+    pub server: ServerEvent,
+}
+
+#[derive(Getters, Clone)]
+pub struct UserTransferEvent {
+    #[cfg(feature = "wasm-plugins")]
+    #[getset(get = "pub")]
+    pub user: NameAndUuid,
+    #[cfg(feature = "wasm-plugins")]
+    #[getset(get = "pub")]
+    pub from: NameAndUuid,
+    #[cfg(feature = "wasm-plugins")]
+    #[getset(get = "pub")]
+    pub to: ServerEvent,
+}
+
+impl UserEvent {
+    #[cfg(feature = "wasm-plugins")]
+    pub fn new(user: NameAndUuid, server: ServerEvent) -> Self {
+        Self { user, server }
+    }
+
+    #[cfg(not(feature = "wasm-plugins"))]
+    pub fn new(_: NameAndUuid, _: ServerEvent) -> Self {
+        Self {}
+    }
+}
+
+impl UserTransferEvent {
+    #[cfg(feature = "wasm-plugins")]
+    pub fn new(user: NameAndUuid, from: NameAndUuid, to: ServerEvent) -> Self {
+        Self { user, from, to }
+    }
+
+    #[cfg(not(feature = "wasm-plugins"))]
+    pub fn new(_: NameAndUuid, _: NameAndUuid, _: ServerEvent) -> Self {
+        Self {}
+    }
+}
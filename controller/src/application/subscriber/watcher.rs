@@ -37,6 +37,32 @@ impl<A: Eq + Hash, B: Clone> Watcher<A, B> {
         count
     }
 
+    // Never waits for a subscriber. Subscribers that can not keep up are dropped, which ends their stream
+    pub async fn try_publish_to_matching<F>(&self, filter: F, message: B) -> u32
+    where
+        F: Fn(&A) -> bool,
+    {
+        let mut count = 0;
+        let mut send = |subscriber: &Subscriber<B>| {
+            let sent = subscriber.try_send_message(message.clone());
+            if sent {
+                count += 1;
+            }
+            sent
+        };
+        for (_, subscribers) in self
+            .1
+            .write()
+            .await
+            .iter_mut()
+            .filter(|(scope, _)| filter(scope))
+        {
+            subscribers.retain(&mut send);
+        }
+        self.0.write().await.retain(&mut send);
+        count
+    }
+
     pub async fn subscribe_to_scope(&self, scope: A, subscriber: Subscriber<B>) {
         self.1
             .write()
@@ -1,20 +1,80 @@
-use getset::Getters;
-use tokio::time::Instant;
+use std::collections::{HashMap, HashSet};
+
+use getset::{Getters, MutGetters};
+use transfer::PendingTransfer;
 
 use super::server::NameAndUuid;
 
+pub mod history;
 pub mod manager;
+pub mod session;
 pub mod transfer;
 
-#[derive(Getters)]
+#[derive(Getters, MutGetters)]
 pub struct User {
     #[getset(get = "pub")]
     id: NameAndUuid,
     #[getset(get = "pub")]
     server: CurrentServer,
+
+    /* Data */
+    #[getset(get = "pub", get_mut = "pub")]
+    data: UserData,
 }
 
 pub enum CurrentServer {
     Connected(NameAndUuid),
-    Transfering((Instant, NameAndUuid)),
+    Transfering(PendingTransfer),
+}
+
+// The data is copied along with every transfer, so it has to stay small
+pub const MAX_NAMESPACES: usize = 16;
+pub const MAX_ENTRIES: usize = 64; // Per namespace
+pub const MAX_KEY_LENGTH: usize = 128; // Also used for namespaces
+pub const MAX_VALUE_LENGTH: usize = 4096;
+
+// Key/value data that servers attach to a user. It follows the user across transfers
+#[derive(Default)]
+pub struct UserData(HashMap<String, HashMap<String, String>>);
+
+impl UserData {
+    // Checks if setting and then removing the given keys stays within the limits
+    pub fn fits(&self, namespace: &str, set: &[(String, String)], remove: &[String]) -> bool {
+        let entries = self.0.get(namespace);
+        let mut keys = HashSet::new();
+        if let Some(entries) = entries {
+            keys.extend(entries.keys().map(String::as_str));
+        }
+        keys.extend(set.iter().map(|(key, _)| key.as_str()));
+        for key in remove {
+            keys.remove(key.as_str());
+        }
+
+        keys.len() <= MAX_ENTRIES
+            && (entries.is_some() || keys.is_empty() || self.0.len() < MAX_NAMESPACES)
+    }
+
+    pub fn set(&mut self, namespace: &str, key: String, value: String) {
+        self.0
+            .entry(namespace.to_string())
+            .or_default()
+            .insert(key, value);
+    }
+
+    pub fn remove(&mut self, namespace: &str, key: &str) {
+        if let Some(entries) = self.0.get_mut(namespace) {
+            entries.remove(key);
+            if entries.is_empty() {
+                self.0.remove(namespace);
+            }
+        }
+    }
+
+    pub fn get_namespace(&self, namespace: &str) -> Option<&HashMap<String, String>> {
+        self.0.get(namespace)
+    }
+
+    pub fn get_namespaces(&self) -> &HashMap<String, HashMap<String, String>> {
+        &self.0
+    }
 }
@@ -0,0 +1,407 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::PathBuf,
+    str::FromStr,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Result;
+use getset::Getters;
+use serde::{Deserialize, Serialize};
+use simplelog::{debug, info, warn};
+use tokio::{
+    sync::{
+        mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel},
+        oneshot,
+    },
+    task::spawn_blocking,
+    time::Instant,
+};
+use uuid::Uuid;
+
+use crate::{
+    application::server::{NameAndUuid, Server},
+    storage::Storage,
+};
+
+// Peaks are stored per hour to keep the log small
+const PEAK_BUCKET: u64 = 3600;
+// Every day gets its own log file, so old history is removed by deleting whole files
+const LOG_SPAN: u64 = 86400;
+const LOG_EXTENSION: &str = ".jsonl";
+const PRUNE_INTERVAL: Duration = Duration::from_secs(3600);
+
+// Records where and how long users played. Only the sessions of online users are kept in memory,
+// everything else is appended to a log that is read when statistics are queried
+pub struct SessionTracker {
+    open: HashMap<Uuid, LoggedSession>,
+    peak: Option<PeakSample>,
+
+    /* Persistence */
+    writer: SessionWriter,
+    last_prune: Instant,
+}
+
+// Everything that was logged, merged into one session list per user
+pub struct History {
+    users: HashMap<Uuid, UserHistory>,
+    peaks: Vec<PeakSample>,
+}
+
+#[derive(Getters)]
+pub struct UserHistory {
+    #[getset(get = "pub")]
+    name: String,
+    #[getset(get = "pub")]
+    sessions: Vec<SessionRecord>,
+}
+
+#[derive(Serialize, Deserialize, Getters, Clone)]
+pub struct SessionRecord {
+    #[getset(get = "pub")]
+    server: String,
+    #[getset(get = "pub")]
+    group: Option<String>,
+    #[getset(get = "pub")]
+    connected: u64,
+    #[getset(get = "pub")]
+    disconnected: Option<u64>,
+    // The session ended because the user was moved to another server
+    #[getset(get = "pub")]
+    transferred: bool,
+}
+
+#[derive(Serialize, Deserialize, Getters, Clone)]
+pub struct PeakSample {
+    #[getset(get = "pub")]
+    timestamp: u64,
+    #[getset(get = "pub")]
+    users: u32,
+}
+
+// A session is logged when it starts and again when it ends. The later line replaces the earlier one
+#[derive(Serialize, Deserialize, Clone)]
+struct LoggedSession {
+    user: Uuid,
+    name: String,
+    #[serde(flatten)]
+    session: SessionRecord,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum LogEntry {
+    Session(LoggedSession),
+    Peak(PeakSample),
+}
+
+// Owns the log file on its own thread so the controller loop never waits for the disk
+#[derive(Clone)]
+struct SessionWriter(UnboundedSender<SessionCommand>);
+
+enum SessionCommand {
+    Append(LogEntry),
+    Prune(u64),
+    Flush(oneshot::Sender<()>),
+}
+
+impl SessionTracker {
+    pub async fn load() -> Result<Self> {
+        info!("Loading session history...");
+        let history = spawn_blocking(History::read).await??;
+        let writer = SessionWriter::spawn()?;
+
+        // The controller did not shut down cleanly, so the real duration is unknown
+        let mut dangling = 0;
+        for (uuid, user) in &history.users {
+            for session in user
+                .sessions
+                .iter()
+                .filter(|session| session.disconnected.is_none())
+            {
+                let mut session = session.clone();
+                session.disconnected = Some(session.connected);
+                writer.append(LogEntry::Session(LoggedSession {
+                    user: *uuid,
+                    name: user.name.clone(),
+                    session,
+                }));
+                dangling += 1;
+            }
+        }
+        if dangling > 0 {
+            warn!("Closed {} session(s) left open by the last run", dangling);
+        }
+
+        info!("Loaded session history of {} user(s)", history.users.len());
+        Ok(Self {
+            open: HashMap::new(),
+            peak: None,
+            writer,
+            last_prune: Instant::now(),
+        })
+    }
+
+    pub fn connected(&mut self, user: &NameAndUuid, server: &Server) {
+        let entry = LoggedSession {
+            user: *user.uuid(),
+            name: user.name().clone(),
+            session: SessionRecord {
+                server: server.id().name().clone(),
+                group: server.group().clone(),
+                connected: unix_now(),
+                disconnected: None,
+                transferred: false,
+            },
+        };
+        self.writer.append(LogEntry::Session(entry.clone()));
+        self.open.insert(*user.uuid(), entry);
+    }
+
+    pub fn transferred(&mut self, user: &NameAndUuid, server: &Server) {
+        self.close(user.uuid(), true);
+        self.connected(user, server);
+    }
+
+    pub fn disconnected(&mut self, uuid: &Uuid) {
+        self.close(uuid, false);
+    }
+
+    pub fn sample(&mut self, users: u32) {
+        let bucket = unix_now() / PEAK_BUCKET * PEAK_BUCKET;
+        if self
+            .peak
+            .as_ref()
+            .is_some_and(|peak| peak.timestamp == bucket && peak.users >= users)
+        {
+            return;
+        }
+        let peak = self.peak.insert(PeakSample {
+            timestamp: bucket,
+            users,
+        });
+        self.writer.append(LogEntry::Peak(peak.clone()));
+    }
+
+    fn close(&mut self, uuid: &Uuid, transferred: bool) {
+        if let Some(mut entry) = self.open.remove(uuid) {
+            entry.session.disconnected = Some(unix_now());
+            entry.session.transferred = transferred;
+            self.writer.append(LogEntry::Session(entry));
+        }
+    }
+}
+
+// Ticking
+impl SessionTracker {
+    pub fn tick(&mut self, retention: &Duration) {
+        if self.last_prune.elapsed() >= PRUNE_INTERVAL {
+            self.last_prune = Instant::now();
+            self.writer
+                .prune(unix_now().saturating_sub(retention.as_secs()));
+        }
+    }
+
+    pub async fn cleanup(&mut self) {
+        let online = self.open.keys().copied().collect::<Vec<_>>();
+        for uuid in online {
+            self.close(&uuid, false);
+        }
+        self.writer.flush().await;
+    }
+}
+
+impl History {
+    // Blocking, because it reads every log file
+    pub fn read() -> Result<Self> {
+        let mut users = HashMap::<Uuid, (String, BTreeMap<(u64, String), SessionRecord>)>::new();
+        let mut peaks = BTreeMap::<u64, u32>::new();
+        for (_, path) in Self::files()? {
+            for line in BufReader::new(File::open(&path)?).lines() {
+                // The last line is cut off if the controller did not stop cleanly
+                let entry = match serde_json::from_str::<LogEntry>(&line?) {
+                    Ok(entry) => entry,
+                    Err(error) => {
+                        debug!("Skipping unreadable line in {:?}: {}", path, error);
+                        continue;
+                    }
+                };
+                match entry {
+                    LogEntry::Session(entry) => {
+                        let (name, sessions) = users.entry(entry.user).or_default();
+                        // Names can change between sessions
+                        *name = entry.name;
+                        sessions.insert(
+                            (entry.session.connected, entry.session.server.clone()),
+                            entry.session,
+                        );
+                    }
+                    LogEntry::Peak(sample) => {
+                        let users = peaks.entry(sample.timestamp).or_default();
+                        *users = (*users).max(sample.users);
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            users: users
+                .into_iter()
+                .map(|(uuid, (name, sessions))| {
+                    (
+                        uuid,
+                        UserHistory {
+                            name,
+                            sessions: sessions.into_values().collect(),
+                        },
+                    )
+                })
+                .collect(),
+            peaks: peaks
+                .into_iter()
+                .map(|(timestamp, users)| PeakSample { timestamp, users })
+                .collect(),
+        })
+    }
+
+    pub fn get_user(&self, uuid: &Uuid) -> Option<&UserHistory> {
+        self.users.get(uuid)
+    }
+
+    pub fn get_users(&self) -> impl Iterator<Item = (&Uuid, &UserHistory)> {
+        self.users.iter()
+    }
+
+    // Highest amount of concurrent users in any hour that overlaps the given timestamp
+    pub fn get_peak(&self, since: u64) -> Option<&PeakSample> {
+        self.peaks
+            .iter()
+            .filter(|sample| sample.timestamp + PEAK_BUCKET > since)
+            .max_by_key(|sample| sample.users)
+    }
+
+    // Sessions that started before the cutoff survive as long as their end was logged later
+    fn prune(cutoff: u64) -> Result<()> {
+        for (day, path) in Self::files()? {
+            if (day + 1) * LOG_SPAN <= cutoff {
+                fs::remove_file(&path)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Returns the log files sorted by their day
+    fn files() -> Result<Vec<(u64, PathBuf)>> {
+        let directory = Storage::sessions_directory();
+        if !directory.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut files = vec![];
+        for entry in fs::read_dir(&directory)? {
+            let path = entry?.path();
+            match path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(LOG_EXTENSION))
+                .map(u64::from_str)
+            {
+                Some(Ok(day)) => files.push((day, path)),
+                _ => {
+                    warn!("Unexpected file {:?} in session history", path);
+                }
+            }
+        }
+        files.sort_by_key(|(day, _)| *day);
+        Ok(files)
+    }
+}
+
+impl SessionWriter {
+    fn spawn() -> Result<Self> {
+        let (sender, receiver) = unbounded_channel();
+        thread::Builder::new()
+            .name("session-history".to_string())
+            .spawn(move || Self::run(receiver))?;
+        Ok(Self(sender))
+    }
+
+    // The receiver lives as long as the tracker
+    fn append(&self, entry: LogEntry) {
+        let _ = self.0.send(SessionCommand::Append(entry));
+    }
+
+    fn prune(&self, cutoff: u64) {
+        let _ = self.0.send(SessionCommand::Prune(cutoff));
+    }
+
+    // Resolves once everything that was appended before is on disk
+    async fn flush(&self) {
+        let (sender, receiver) = oneshot::channel();
+        if self.0.send(SessionCommand::Flush(sender)).is_ok() {
+            let _ = receiver.await;
+        }
+    }
+
+    fn run(mut receiver: UnboundedReceiver<SessionCommand>) {
+        let mut file = None;
+        while let Some(command) = receiver.blocking_recv() {
+            match command {
+                SessionCommand::Append(entry) => {
+                    if let Err(error) = Self::write(&mut file, &entry) {
+                        warn!("Failed to write session history: {}", error);
+                        file = None;
+                    }
+                }
+                SessionCommand::Prune(cutoff) => {
+                    if let Err(error) = History::prune(cutoff) {
+                        warn!("Failed to remove old session history: {}", error);
+                    }
+                }
+                SessionCommand::Flush(sender) => {
+                    // Every line is written without buffering, so there is nothing left to do
+                    let _ = sender.send(());
+                }
+            }
+        }
+        debug!("Session tracker dropped, stopping the session history thread");
+    }
+
+    fn write(file: &mut Option<(u64, File)>, entry: &LogEntry) -> Result<()> {
+        let day = unix_now() / LOG_SPAN;
+        let file = match file {
+            Some((current, file)) if *current == day => file,
+            _ => {
+                fs::create_dir_all(Storage::sessions_directory())?;
+                let handle = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(Storage::session_log_file(day))?;
+                &mut file.insert((day, handle)).1
+            }
+        };
+
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+        file.write_all(&line)?;
+        Ok(())
+    }
+}
+
+impl SessionRecord {
+    // Seconds the user spent on the server after the given timestamp
+    pub fn playtime(&self, since: u64, now: u64) -> u64 {
+        self.disconnected
+            .unwrap_or(now)
+            .saturating_sub(self.connected.max(since))
+    }
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 use anyhow::Result;
 use simplelog::{info, warn};
@@ -6,46 +6,89 @@ use uuid::Uuid;
 
 use crate::{
     application::{
+        Shared,
         auth::ActionResult,
-        server::{NameAndUuid, Server},
+        server::{NameAndUuid, Server, manager::ServerManager},
+        subscriber::manager::event::user::{UserEvent, UserTransferEvent},
     },
     config::Config,
+    network::client::TransferState,
 };
 
-use super::{CurrentServer, User};
+use super::{CurrentServer, User, UserData, history::SessionTracker, session::LastSession};
 
 pub struct UserManager {
     users: HashMap<Uuid, User>,
+
+    /* Sticky sessions */
+    sessions: HashMap<Uuid, LastSession>,
+
+    /* Statistics */
+    history: SessionTracker,
 }
 
 impl UserManager {
-    pub fn init() -> Self {
-        Self {
+    pub async fn init() -> Result<Self> {
+        Ok(Self {
             users: HashMap::new(),
-        }
+            sessions: HashMap::new(),
+            history: SessionTracker::load().await?,
+        })
     }
 
-    pub fn remove_users_on_server(&mut self, server: &Uuid) -> u32 {
+    pub async fn remove_users_on_server(&mut self, server: &Server, shared: &Arc<Shared>) -> u32 {
         let mut amount = 0;
+        let mut removed = vec![];
+        let mut failed = vec![];
+        let uuid = server.id().uuid();
         self.users.retain(|_, user| {
-            if let CurrentServer::Connected(current) = &user.server
-                && current.uuid() == server
-            {
-                info!(
-                    "User {}[{}] disconnected from server {}",
-                    user.id,
-                    user.id.uuid().to_string(),
-                    current.name(),
-                );
-                amount += 1;
-                return false;
+            match &mut user.server {
+                CurrentServer::Connected(current) if current.uuid() == uuid => {
+                    info!(
+                        "User {}[{}] disconnected from server {}",
+                        user.id,
+                        user.id.uuid().to_string(),
+                        current.name(),
+                    );
+                    self.sessions
+                        .insert(*user.id.uuid(), LastSession::from(server));
+                    self.history.disconnected(user.id.uuid());
+                    removed.push(user.id.clone());
+                    amount += 1;
+                    return false;
+                }
+                CurrentServer::Transfering(transfer) if transfer.to().uuid() == uuid => {
+                    failed.push(*user.id.uuid());
+                }
+                CurrentServer::Transfering(transfer) if transfer.from().uuid() == uuid => {
+                    transfer.mark_left();
+                }
+                CurrentServer::Connected(_) | CurrentServer::Transfering(_) => {}
             }
             true
         });
+        for user in failed {
+            self.fail_transfer(&user, TransferState::Rejected, "Target server stopped");
+        }
+
+        for user in removed {
+            shared
+                .subscribers
+                .plugin()
+                .user_disconnected()
+                .publish(UserEvent::new(user, server.into()))
+                .await;
+        }
         amount
     }
 
-    pub fn user_connected(&mut self, server: &mut Server, id: NameAndUuid) {
+    // Returns the server that no longer has to hold a slot for this user
+    pub async fn user_connected(
+        &mut self,
+        server: &mut Server,
+        id: NameAndUuid,
+        shared: &Arc<Shared>,
+    ) -> Option<Uuid> {
         // Update server user count
         server.set_connected_users(server.connected_users() + 1);
 
@@ -60,16 +103,45 @@ impl UserManager {
                         server.id(),
                     );
                 }
-                CurrentServer::Transfering(_) => {
+                CurrentServer::Transfering(transfer) if transfer.to() == server.id() => {
                     info!(
                         "User {}[{}] successfully transferred to server {}",
                         id,
                         id.uuid().to_string(),
                         server.id(),
                     );
+                    transfer.report(id.uuid(), TransferState::Succeeded, None);
+                }
+                CurrentServer::Transfering(transfer) => {
+                    warn!(
+                        "User {}[{}] was transferring to server {} but connected to server {}",
+                        id,
+                        id.uuid().to_string(),
+                        transfer.to(),
+                        server.id(),
+                    );
+                    transfer.report(
+                        id.uuid(),
+                        TransferState::Rejected,
+                        Some(format!("User connected to server {} instead", server.id())),
+                    );
                 }
             }
+            self.history.transferred(&id, server);
+            let (from, released) = match &user.server {
+                CurrentServer::Connected(current) => (current.clone(), None),
+                CurrentServer::Transfering(transfer) => {
+                    (transfer.from().clone(), Some(*transfer.to().uuid()))
+                }
+            };
             user.server = CurrentServer::Connected(server.id().clone());
+            shared
+                .subscribers
+                .plugin()
+                .user_transferred()
+                .publish(UserTransferEvent::new(id, from, (&*server).into()))
+                .await;
+            released
         } else {
             info!(
                 "User {}[{}] connected to server {}",
@@ -77,35 +149,67 @@ impl UserManager {
                 id.uuid().to_string(),
                 server.id()
             );
+            self.sessions.remove(id.uuid());
+            self.history.connected(&id, server);
+            shared
+                .subscribers
+                .plugin()
+                .user_connected()
+                .publish(UserEvent::new(id.clone(), (&*server).into()))
+                .await;
             self.users.insert(
                 *id.uuid(),
                 User {
                     id,
                     server: CurrentServer::Connected(server.id().clone()),
+                    data: UserData::default(),
                 },
             );
+            None
         }
     }
 
-    pub fn user_disconnected(&mut self, server: &mut Server, uuid: &Uuid) -> ActionResult {
+    pub async fn user_disconnected(
+        &mut self,
+        server: &mut Server,
+        uuid: &Uuid,
+        shared: &Arc<Shared>,
+    ) -> ActionResult {
         // Update server user count
         server.set_connected_users(server.connected_users() - 1);
 
         // Update internal user list
-        if let Some(user) = self.users.get(uuid)
-            && let CurrentServer::Connected(current) = &user.server
-        {
-            // Verify that the user is connected to the server
-            if current.uuid() == server.id().uuid() {
-                info!(
-                    "User {}[{}] disconnected from server {}",
-                    user.id,
-                    user.id.uuid().to_string(),
-                    server.id(),
-                );
-                self.users.remove(uuid);
-            } else {
-                return ActionResult::Denied;
+        if let Some(user) = self.users.get_mut(uuid) {
+            match &mut user.server {
+                CurrentServer::Connected(current) => {
+                    // Verify that the user is connected to the server
+                    if current.uuid() == server.id().uuid() {
+                        info!(
+                            "User {}[{}] disconnected from server {}",
+                            user.id,
+                            user.id.uuid().to_string(),
+                            server.id(),
+                        );
+                        let user = user.id.clone();
+                        self.users.remove(uuid);
+                        self.sessions.insert(*uuid, LastSession::from(&*server));
+                        self.history.disconnected(uuid);
+                        shared
+                            .subscribers
+                            .plugin()
+                            .user_disconnected()
+                            .publish(UserEvent::new(user, (&*server).into()))
+                            .await;
+                    } else {
+                        return ActionResult::Denied;
+                    }
+                }
+                CurrentServer::Transfering(transfer) => {
+                    // The user is on the way, only remember that there is no way back anymore
+                    if transfer.from() == server.id() {
+                        transfer.mark_left();
+                    }
+                }
             }
         }
         ActionResult::Allowed
@@ -130,31 +234,90 @@ impl UserManager {
     pub fn get_user_mut(&mut self, uuid: &Uuid) -> Option<&mut User> {
         self.users.get_mut(uuid)
     }
+
+    pub fn get_last_session(&self, uuid: &Uuid) -> Option<&LastSession> {
+        self.sessions.get(uuid)
+    }
+
+    // Puts the user back on the previous server or forgets the user if that server was already left
+    fn fail_transfer(&mut self, uuid: &Uuid, state: TransferState, message: &str) {
+        let Some(user) = self.users.get_mut(uuid) else {
+            return;
+        };
+        let CurrentServer::Transfering(transfer) = &user.server else {
+            return;
+        };
+        transfer.report(user.id.uuid(), state, Some(message.to_string()));
+
+        if *transfer.left() {
+            info!(
+                "User {}[{}] already left server {}, removing user",
+                user.id,
+                user.id.uuid(),
+                transfer.from(),
+            );
+            self.sessions
+                .insert(*uuid, LastSession::new(transfer.from().clone(), None));
+            self.history.disconnected(uuid);
+            self.users.remove(uuid);
+        } else {
+            info!(
+                "User {}[{}] is still connected to server {}, restoring previous server",
+                user.id,
+                user.id.uuid(),
+                transfer.from(),
+            );
+            user.server = CurrentServer::Connected(transfer.from().clone());
+        }
+    }
 }
 
 // Ticking
 impl UserManager {
     #[allow(clippy::unnecessary_wraps)]
-    pub fn tick(&mut self, config: &Config) -> Result<()> {
-        self.users.retain(|_, user| {
-            if let CurrentServer::Transfering((timestamp, to)) = &user.server
-                && timestamp.elapsed() >= *config.transfer_timeout()
-            {
-                warn!(
-                    "User {}[{}] transfer to server {} timed out",
-                    user.id,
-                    user.id.uuid(),
-                    to,
-                );
-                return false;
+    pub fn tick(&mut self, config: &Config, servers: &mut ServerManager) -> Result<()> {
+        let expired = self
+            .users
+            .values()
+            .filter_map(|user| match &user.server {
+                CurrentServer::Transfering(transfer)
+                    if transfer.timestamp().elapsed() >= *config.transfer_timeout() =>
+                {
+                    warn!(
+                        "User {}[{}] transfer to server {} timed out",
+                        user.id,
+                        user.id.uuid(),
+                        transfer.to(),
+                    );
+                    Some((*user.id.uuid(), *transfer.to().uuid()))
+                }
+                CurrentServer::Connected(_) | CurrentServer::Transfering(_) => None,
+            })
+            .collect::<Vec<_>>();
+        for (user, target) in expired {
+            if let Some(server) = servers.get_server_mut(&target) {
+                server.release_slot();
             }
-            true
-        });
+            self.fail_transfer(
+                &user,
+                TransferState::TimedOut,
+                "User did not arrive in time",
+            );
+        }
+
+        // Forget sessions that are outside of the reconnect grace period
+        self.sessions
+            .retain(|_, session| session.since().elapsed() < *config.reconnect_grace());
+
+        // Record the peak and prune the log once in a while
+        self.history.sample(self.get_user_count());
+        self.history.tick(config.history_retention());
         Ok(())
     }
 
-    #[allow(clippy::unnecessary_wraps, clippy::unused_self)]
-    pub fn cleanup(&mut self) -> Result<()> {
+    #[allow(clippy::unnecessary_wraps)]
+    pub async fn cleanup(&mut self) -> Result<()> {
+        self.history.cleanup().await;
         Ok(())
     }
 }
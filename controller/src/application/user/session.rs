@@ -0,0 +1,31 @@
+use getset::Getters;
+use tokio::time::Instant;
+
+use crate::application::server::{NameAndUuid, Server};
+
+// The server a user was on before disconnecting. Used to route reconnects back
+#[derive(Getters)]
+pub struct LastSession {
+    #[getset(get = "pub")]
+    server: NameAndUuid,
+    #[getset(get = "pub")]
+    group: Option<String>,
+    #[getset(get = "pub")]
+    since: Instant,
+}
+
+impl LastSession {
+    pub fn new(server: NameAndUuid, group: Option<String>) -> Self {
+        Self {
+            server,
+            group,
+            since: Instant::now(),
+        }
+    }
+}
+
+impl From<&Server> for LastSession {
+    fn from(server: &Server) -> Self {
+        Self::new(server.id().clone(), server.group().clone())
+    }
+}
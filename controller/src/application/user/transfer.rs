@@ -1,15 +1,23 @@
 use std::sync::Arc;
 
+use getset::Getters;
 use simplelog::info;
-use tokio::time::Instant;
+use tokio::{
+    sync::mpsc::{UnboundedSender, unbounded_channel},
+    time::Instant,
+};
+use tokio_stream::wrappers::UnboundedReceiverStream;
 use tonic::Status;
 use uuid::Uuid;
 
-use crate::application::{
-    Shared,
-    auth::Authorization,
-    group::manager::GroupManager,
-    server::{NameAndUuid, Server, manager::ServerManager},
+use crate::{
+    application::{
+        Shared,
+        auth::Authorization,
+        group::manager::GroupManager,
+        server::{NameAndUuid, Server, manager::ServerManager},
+    },
+    network::client::{TransferState, TransferStatusMsg},
 };
 
 use super::{CurrentServer, User};
@@ -21,7 +29,25 @@ impl<'a> Transfer<'a> {
         target: &TransferTarget,
         servers: &'a ServerManager,
         groups: &GroupManager,
+        reporter: &TransferReporter,
     ) -> Result<Transfer<'a>, ResolveError> {
+        let from = Self::check_source(auth, user)?.clone();
+        let to = Self::resolve_target(target, &[*from.uuid()], servers, groups, 1)?;
+
+        Ok(Transfer::new(
+            Uuid::new_v4(),
+            user,
+            from,
+            to,
+            reporter.clone(),
+        ))
+    }
+
+    // Returns the server the user is currently connected to
+    pub fn check_source<'b>(
+        auth: &Authorization,
+        user: &'b User,
+    ) -> Result<&'b NameAndUuid, ResolveError> {
         // Check if auth is allowed to transfer user
         if let Some(server) = auth.get_server() {
             if let CurrentServer::Connected(current) = &user.server {
@@ -36,23 +62,35 @@ impl<'a> Transfer<'a> {
         let CurrentServer::Connected(from) = &user.server else {
             return Err(ResolveError::UserNotFound);
         };
+        Ok(from)
+    }
 
-        let to = match target {
+    // Finds a server that has room for the given amount of users
+    pub fn resolve_target(
+        target: &TransferTarget,
+        sources: &[Uuid],
+        servers: &'a ServerManager,
+        groups: &GroupManager,
+        slots: u32,
+    ) -> Result<&'a Server, ResolveError> {
+        match target {
             TransferTarget::Server(to) => {
-                servers.get_server(to).ok_or(ResolveError::ServerNotFound)?
-            }
-            TransferTarget::Group(group) => {
-                let group = groups.get_group(group).ok_or(ResolveError::GroupNotFound)?;
-                group
-                    .find_free_server(servers)
-                    .ok_or(ResolveError::NotServerAvailable)?
+                let server = servers.get_server(to).ok_or(ResolveError::ServerNotFound)?;
+                if server.fits(slots) {
+                    Ok(server)
+                } else {
+                    Err(ResolveError::NotServerAvailable)
+                }
             }
+            TransferTarget::Group(group) => groups
+                .get_group(group)
+                .ok_or(ResolveError::GroupNotFound)?
+                .find_free_server(servers, slots)
+                .ok_or(ResolveError::NotServerAvailable),
             TransferTarget::Fallback => servers
-                .find_fallback_server(from.uuid())
-                .ok_or(ResolveError::NotServerAvailable)?,
-        };
-
-        Ok(Transfer::new(user, from.clone(), to, Instant::now()))
+                .find_fallback_server(sources, slots)
+                .ok_or(ResolveError::NotServerAvailable),
+        }
     }
 
     pub async fn transfer_user(
@@ -65,7 +103,7 @@ impl<'a> Transfer<'a> {
             transfer.from,
             transfer.to.id()
         );
-        if let Some(data) = transfer.to.new_transfer(transfer.user.id.uuid()) {
+        if let Some(data) = transfer.to.new_transfer(&transfer.id, transfer.user) {
             shared
                 .subscribers
                 .network()
@@ -73,13 +111,27 @@ impl<'a> Transfer<'a> {
                 .publish_to_scope(transfer.from.uuid(), data)
                 .await;
 
-            transfer.user.server =
-                CurrentServer::Transfering((transfer.timestamp, transfer.to.id().clone()));
+            let pending = PendingTransfer {
+                id: transfer.id,
+                timestamp: Instant::now(),
+                from: transfer.from.clone(),
+                to: transfer.to.id().clone(),
+                left: false,
+                reporter: transfer.reporter.clone(),
+            };
+            pending.report(transfer.user.id().uuid(), TransferState::Pending, None);
+            transfer.user.server = CurrentServer::Transfering(pending);
             Ok(())
         } else {
-            Err(Status::unavailable(
-                "Target server seems to have no network address",
-            ))
+            let error = Status::unavailable("Target server seems to have no network address");
+            transfer.reporter.report(
+                &transfer.id,
+                transfer.user.id().uuid(),
+                Some(transfer.to.id()),
+                TransferState::Rejected,
+                Some(error.message().to_string()),
+            );
+            Err(error)
         }
     }
 }
@@ -100,19 +152,100 @@ pub enum TransferTarget {
 }
 
 pub struct Transfer<'a> {
+    id: Uuid,
     user: &'a mut User,
     from: NameAndUuid,
     to: &'a Server,
+    reporter: TransferReporter,
+}
+
+// A transfer that was sent to the previous server and waits for the user to arrive
+#[derive(Getters)]
+pub struct PendingTransfer {
+    #[getset(get = "pub")]
+    id: Uuid,
+    #[getset(get = "pub")]
     timestamp: Instant,
+    #[getset(get = "pub")]
+    from: NameAndUuid,
+    #[getset(get = "pub")]
+    to: NameAndUuid,
+    // The previous server reported that the user left it
+    #[getset(get = "pub")]
+    left: bool,
+    reporter: TransferReporter,
 }
 
+// Reports the progress of a transfer back to whoever requested it.
+// Reports are sent from the controller loop, so they are queued instead of waiting for the caller to read them
+#[derive(Clone, Default)]
+pub struct TransferReporter(Option<UnboundedSender<Result<TransferStatusMsg, Status>>>);
+
 impl<'a> Transfer<'a> {
-    fn new(user: &'a mut User, from: NameAndUuid, to: &'a Server, timestamp: Instant) -> Self {
+    pub fn new(
+        id: Uuid,
+        user: &'a mut User,
+        from: NameAndUuid,
+        to: &'a Server,
+        reporter: TransferReporter,
+    ) -> Self {
         Self {
+            id,
             user,
             from,
             to,
-            timestamp,
+            reporter,
+        }
+    }
+
+    pub fn to(&self) -> &'a Server {
+        self.to
+    }
+}
+
+impl PendingTransfer {
+    pub fn mark_left(&mut self) {
+        self.left = true;
+    }
+
+    pub fn report(&self, user: &Uuid, state: TransferState, message: Option<String>) {
+        self.reporter
+            .report(&self.id, user, Some(&self.to), state, message);
+    }
+}
+
+impl TransferReporter {
+    pub fn create() -> (
+        Self,
+        UnboundedReceiverStream<Result<TransferStatusMsg, Status>>,
+    ) {
+        let (sender, receiver) = unbounded_channel();
+        (Self(Some(sender)), UnboundedReceiverStream::new(receiver))
+    }
+
+    pub fn fail(&self, error: Status) {
+        if let Some(sender) = &self.0 {
+            let _ = sender.send(Err(error));
+        }
+    }
+
+    pub fn report(
+        &self,
+        id: &Uuid,
+        user: &Uuid,
+        server: Option<&NameAndUuid>,
+        state: TransferState,
+        message: Option<String>,
+    ) {
+        if let Some(sender) = &self.0 {
+            // The caller might have stopped listening, which is fine
+            let _ = sender.send(Ok(TransferStatusMsg {
+                id: id.to_string(),
+                user: user.to_string(),
+                state: state as i32,
+                server: server.map(|server| server.uuid().to_string()),
+                message,
+            }));
         }
     }
 }
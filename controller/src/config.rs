@@ -31,11 +31,52 @@ struct Timeouts {
     empty_server: Duration,
 }
 
+#[derive(Deserialize)]
+struct Channels {
+    history: usize,
+    retention: Duration,
+    // Older configs do not contain this field
+    #[serde(default = "Channels::default_consumer_retention")]
+    consumer_retention: Duration,
+}
+
+#[derive(Deserialize)]
+struct Sessions {
+    reconnect_grace: Duration,
+}
+
+#[derive(Deserialize)]
+struct History {
+    retention: Duration,
+}
+
+#[derive(Deserialize)]
+struct Logs {
+    archive: bool,
+    retention: Duration,
+}
+
+#[derive(Deserialize, Default)]
+struct Plugins {
+    watch: bool,
+}
+
 #[derive(Deserialize)]
 pub struct Config {
     identifier: String,
     network: Network,
     timeouts: Timeouts,
+    // Older configs do not contain this section
+    #[serde(default)]
+    channels: Channels,
+    #[serde(default)]
+    sessions: Sessions,
+    #[serde(default)]
+    history: History,
+    #[serde(default)]
+    logs: Logs,
+    #[serde(default)]
+    plugins: Plugins,
 }
 
 impl Config {
@@ -91,6 +132,79 @@ impl Config {
     pub fn empty_server_timeout(&self) -> &Duration {
         &self.timeouts.empty_server
     }
+
+    pub fn channel_history(&self) -> usize {
+        self.channels.history
+    }
+
+    pub fn channel_retention(&self) -> &Duration {
+        &self.channels.retention
+    }
+
+    pub fn consumer_retention(&self) -> &Duration {
+        &self.channels.consumer_retention
+    }
+
+    pub fn reconnect_grace(&self) -> &Duration {
+        &self.sessions.reconnect_grace
+    }
+
+    pub fn history_retention(&self) -> &Duration {
+        &self.history.retention
+    }
+
+    pub fn archive_logs(&self) -> bool {
+        self.logs.archive
+    }
+
+    pub fn log_retention(&self) -> &Duration {
+        &self.logs.retention
+    }
+
+    pub fn watch_plugins(&self) -> bool {
+        self.plugins.watch
+    }
+}
+
+impl Default for Channels {
+    fn default() -> Self {
+        Self {
+            history: 256,
+            retention: Duration::from_secs(300),
+            consumer_retention: Self::default_consumer_retention(),
+        }
+    }
+}
+
+impl Channels {
+    fn default_consumer_retention() -> Duration {
+        Duration::from_hours(168)
+    }
 }
 
 impl LoadFromTomlFile for Config {}
+
+impl Default for Sessions {
+    fn default() -> Self {
+        Self {
+            reconnect_grace: Duration::from_secs(120),
+        }
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self {
+            retention: Duration::from_hours(720),
+        }
+    }
+}
+
+impl Default for Logs {
+    fn default() -> Self {
+        Self {
+            archive: false,
+            retention: Duration::from_hours(168),
+        }
+    }
+}
@@ -21,9 +21,14 @@ use crate::{application::Shared, config::Config, task::manager::TaskSender};
 
 mod auth;
 pub mod client;
+mod group;
+pub mod handshake;
 pub mod manage;
+mod page;
 mod proto;
+mod server;
 pub mod tls;
+mod user;
 
 pub struct NetworkStack {
     shutdown: Sender<bool>,
@@ -4,11 +4,16 @@ use tonic::{Request, Status, service::Interceptor};
 
 use crate::application::Shared;
 
+use super::handshake::Protocol;
+
 #[derive(Clone)]
 pub struct AuthInterceptor(pub Arc<Shared>);
 
 impl Interceptor for AuthInterceptor {
     fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        let protocol = Protocol::from_metadata(request.metadata())?;
+        request.extensions_mut().insert(protocol);
+
         let metadata = request.metadata();
         let token = metadata.get("authorization").and_then(|t| t.to_str().ok());
         if let Some(token) = token {
@@ -2,41 +2,54 @@ use std::{str::FromStr, sync::Arc};
 
 use anyhow::Result;
 use beat::BeatTask;
-use group::{GetGroupTask, GetGroupsTask};
+use group::GetGroupTask;
 use health::{RequestStopTask, SetRunningTask};
 use ready::SetReadyTask;
 use server::{GetServerFromNameTask, GetServerTask, GetServersTask};
-use tokio_stream::wrappers::ReceiverStream;
+use tokio::spawn;
+use tokio_stream::wrappers::{ReceiverStream, UnboundedReceiverStream};
 use tonic::{Request, Response, Status, async_trait};
 use user::{
-    GetUserFromNameTask, GetUserTask, GetUsersTask, UserConnectedTask, UserCountTask,
-    UserDisconnectedTask,
+    GetUserDataTask, GetUserFromNameTask, GetUserTask, ResolveReconnectTask, SetUserDataTask,
+    UserConnectedTask, UserCountTask, UserDisconnectedTask,
 };
 use uuid::Uuid;
 
 use crate::{
     VERSION,
     application::{
-        Shared, auth::AuthType, server::NameAndUuid, subscriber::Subscriber,
-        user::transfer::TransferTarget,
+        Shared,
+        auth::{AuthType, Authorization},
+        channel::{StartPoint, pattern::check_name},
+        server::{NameAndUuid, selector::ServerSelector},
+        subscriber::Subscriber,
+        user::{
+            MAX_ENTRIES, MAX_KEY_LENGTH, MAX_VALUE_LENGTH,
+            transfer::{TransferReporter, TransferTarget},
+        },
     },
-    task::{manager::TaskSender, network::TonicTask},
+    task::{BoxedTask, manager::TaskSender, network::TonicTask},
 };
 
 use super::{
+    group::GetGroupsTask,
+    handshake::{Protocol, negotiate},
     manage::transfer::TransferUsersTask,
+    page::Pagination,
     proto::{
         client::{
-            channel::Msg,
+            self, Action,
+            channel::{self, AckReq, Msg, SubscribeReq, subscribe_req::Start},
             client_service_server::ClientService,
-            transfer::{TransferReq, TransferRes, target::Type},
-            user::{ConnectedReq, DisconnectedReq},
+            transfer::{QueueUpdate, TransferReq, TransferRes, target::Type},
+            user::{ConnectedReq, Data, DisconnectedReq, GetDataReq, SetDataReq},
         },
         common::{
-            common_group, common_server, common_user,
+            common_group, common_server, common_transfer, common_user, handshake,
             notify::{PowerEvent, ReadyEvent},
         },
     },
+    user::GetUsersTask,
 };
 
 mod beat;
@@ -47,8 +60,17 @@ mod ready;
 mod server;
 mod user;
 
+const PUBLISH_DENIED_MESSAGE: &str = "Not allowed to publish to this channel";
+const SUBSCRIBE_DENIED_MESSAGE: &str = "Not allowed to subscribe to this channel";
+
 pub type TransferMsg = TransferRes;
+pub type QueueMsg = QueueUpdate;
+pub type ActionMsg = Action;
+pub type TransferStatusMsg = common_transfer::Status;
+pub type TransferState = common_transfer::status::State;
 pub type ChannelMsg = Msg;
+pub type ChannelRequestMsg = channel::Request;
+pub type ChannelReplyMsg = channel::Reply;
 pub type PowerMsg = PowerEvent;
 pub type ReadyMsg = ReadyEvent;
 
@@ -56,8 +78,12 @@ pub struct ClientServiceImpl(pub TaskSender, pub Arc<Shared>);
 
 #[async_trait]
 impl ClientService for ClientServiceImpl {
+    type TransferUsersWithStatusStream = UnboundedReceiverStream<Result<TransferStatusMsg, Status>>;
     type SubscribeToTransfersStream = ReceiverStream<Result<TransferRes, Status>>;
+    type SubscribeToQueueUpdatesStream = ReceiverStream<Result<QueueUpdate, Status>>;
+    type SubscribeToActionsStream = ReceiverStream<Result<Action, Status>>;
     type SubscribeToChannelStream = ReceiverStream<Result<Msg, Status>>;
+    type SubscribeToRequestsStream = ReceiverStream<Result<ChannelRequestMsg, Status>>;
     type SubscribeToPowerEventsStream = ReceiverStream<Result<PowerEvent, Status>>;
     type SubscribeToReadyEventsStream = ReceiverStream<Result<ReadyEvent, Status>>;
 
@@ -175,13 +201,16 @@ impl ClientService for ClientServiceImpl {
             .await?,
         ))
     }
-    async fn get_users(&self, request: Request<()>) -> Result<Response<common_user::List>, Status> {
+    async fn get_users(
+        &self,
+        request: Request<common_user::ListReq>,
+    ) -> Result<Response<common_user::List>, Status> {
         Ok(Response::new(
             TonicTask::execute::<common_user::List, _, _>(
                 AuthType::Server,
                 &self.0,
                 request,
-                |_, _| Ok(Box::new(GetUsersTask)),
+                |request, _| Ok(Box::new(GetUsersTask::try_from(request)?)),
             )
             .await?,
         ))
@@ -195,53 +224,106 @@ impl ClientService for ClientServiceImpl {
         ))
     }
 
-    // Transfer
-    async fn transfer_users(&self, request: Request<TransferReq>) -> Result<Response<u32>, Status> {
+    async fn set_user_data(&self, request: Request<SetDataReq>) -> Result<Response<()>, Status> {
         Ok(Response::new(
-            TonicTask::execute::<u32, _, _>(AuthType::Server, &self.0, request, |request, auth| {
+            TonicTask::execute::<(), _, _>(AuthType::Server, &self.0, request, |request, _| {
                 let request = request.into_inner();
-
-                let target = match request.target {
-                    Some(target) => match Type::try_from(target.r#type) {
-                        Ok(r#type) => match (target.target, r#type) {
-                            (Some(target), Type::Group) => TransferTarget::Group(target),
-                            (Some(target), Type::Server) => {
-                                TransferTarget::Server(match Uuid::from_str(&target) {
-                                    Ok(uuid) => uuid,
-                                    Err(_) => {
-                                        return Err(Status::invalid_argument(
-                                            "Invalid UUID provided",
-                                        ));
-                                    }
-                                })
-                            }
-                            (None, Type::Fallback) => TransferTarget::Fallback,
-                            _ => {
-                                return Err(Status::invalid_argument(
-                                    "Invalid target type combination",
-                                ));
-                            }
-                        },
-                        Err(_) => {
-                            return Err(Status::invalid_argument("Invalid target type provided"));
-                        }
-                    },
-                    None => return Err(Status::invalid_argument("Missing target")),
+                let Ok(uuid) = Uuid::from_str(&request.id) else {
+                    return Err(Status::invalid_argument("Invalid UUID provided"));
                 };
-                let uuids = request
-                    .ids
-                    .into_iter()
-                    .map(|id| match Uuid::from_str(&id) {
-                        Ok(uuid) => Ok(uuid),
-                        Err(_) => Err(Status::invalid_argument("Invalid UUID provided")),
+                if request.namespace.is_empty() {
+                    return Err(Status::invalid_argument("Namespace can not be empty"));
+                }
+                if request.namespace.len() > MAX_KEY_LENGTH
+                    || request.set.len() > MAX_ENTRIES
+                    || request.set.iter().any(|entry| {
+                        entry.key.len() > MAX_KEY_LENGTH || entry.value.len() > MAX_VALUE_LENGTH
                     })
-                    .collect::<Result<Vec<Uuid>, _>>()?;
+                {
+                    return Err(Status::invalid_argument(format!(
+                        "Namespaces and keys are limited to {MAX_KEY_LENGTH} bytes, values to {MAX_VALUE_LENGTH} bytes and a request to {MAX_ENTRIES} entries"
+                    )));
+                }
+
+                Ok(Box::new(SetUserDataTask(
+                    uuid,
+                    request.namespace,
+                    request
+                        .set
+                        .into_iter()
+                        .map(|entry| (entry.key, entry.value))
+                        .collect(),
+                    request.remove,
+                )))
+            })
+            .await?,
+        ))
+    }
+    async fn get_user_data(&self, request: Request<GetDataReq>) -> Result<Response<Data>, Status> {
+        Ok(Response::new(
+            TonicTask::execute::<Data, _, _>(AuthType::Server, &self.0, request, |request, _| {
+                let request = request.into_inner();
+                let Ok(uuid) = Uuid::from_str(&request.id) else {
+                    return Err(Status::invalid_argument("Invalid UUID provided"));
+                };
+
+                Ok(Box::new(GetUserDataTask(uuid, request.namespace)))
+            })
+            .await?,
+        ))
+    }
 
-                Ok(Box::new(TransferUsersTask(auth, uuids, target)))
+    // Transfer
+    async fn transfer_users(&self, request: Request<TransferReq>) -> Result<Response<u32>, Status> {
+        Ok(Response::new(
+            TonicTask::execute::<u32, _, _>(AuthType::Server, &self.0, request, |request, auth| {
+                transfer_task(request.into_inner(), auth, TransferReporter::default())
             })
             .await?,
         ))
     }
+    async fn transfer_users_with_status(
+        &self,
+        request: Request<TransferReq>,
+    ) -> Result<Response<Self::TransferUsersWithStatusStream>, Status> {
+        let (reporter, receiver) = TransferReporter::create();
+
+        // The status stream has to be returned before the transfers start so that nothing waits for the caller
+        let queue = self.0.clone();
+        spawn(async move {
+            if let Err(error) = TonicTask::execute::<u32, _, _>(
+                AuthType::Server,
+                &queue,
+                request,
+                |request, auth| transfer_task(request.into_inner(), auth, reporter.clone()),
+            )
+            .await
+            {
+                reporter.fail(error);
+            }
+        });
+        Ok(Response::new(receiver))
+    }
+    async fn resolve_reconnect(
+        &self,
+        request: Request<String>,
+    ) -> Result<Response<client::server::Detail>, Status> {
+        Ok(Response::new(
+            TonicTask::execute::<client::server::Detail, _, _>(
+                AuthType::Server,
+                &self.0,
+                request,
+                |request, auth| {
+                    let Ok(uuid) = Uuid::from_str(request.get_ref()) else {
+                        return Err(Status::invalid_argument("Invalid UUID provided"));
+                    };
+
+                    Ok(Box::new(ResolveReconnectTask(auth, uuid)))
+                },
+            )
+            .await?,
+        ))
+    }
     async fn subscribe_to_transfers(
         &self,
         request: Request<()>,
@@ -261,45 +343,182 @@ impl ClientService for ClientServiceImpl {
 
         Ok(Response::new(receiver))
     }
+    async fn subscribe_to_queue_updates(
+        &self,
+        request: Request<()>,
+    ) -> Result<Response<Self::SubscribeToQueueUpdatesStream>, Status> {
+        let auth = TonicTask::get_auth(AuthType::Server, &request)?;
+        let server = auth
+            .get_server()
+            .expect("Should be ok. Because type is checked in get_auth");
+
+        let (sender, receiver) = Subscriber::create_network();
+        self.1
+            .subscribers
+            .network()
+            .queue()
+            .subscribe_to_scope(*server.uuid(), sender)
+            .await;
+
+        Ok(Response::new(receiver))
+    }
+
+    // Action
+    async fn subscribe_to_actions(
+        &self,
+        request: Request<()>,
+    ) -> Result<Response<Self::SubscribeToActionsStream>, Status> {
+        let auth = TonicTask::get_auth(AuthType::Server, &request)?;
+        let server = auth
+            .get_server()
+            .expect("Should be ok. Because type is checked in get_auth");
+
+        let (sender, receiver) = Subscriber::create_network();
+        self.1
+            .subscribers
+            .network()
+            .action()
+            .subscribe_to_scope(*server.uuid(), sender)
+            .await;
+
+        Ok(Response::new(receiver))
+    }
 
     // Channel
     async fn publish_message(&self, request: Request<Msg>) -> Result<Response<u32>, Status> {
+        let auth = TonicTask::get_auth(AuthType::Server, &request)?;
         let request = request.into_inner();
-        let channel = request.channel.clone();
+        check_name(&request.channel)?;
+        if !auth
+            .get_server()
+            .is_some_and(|server| server.channels().can_publish(&request.channel))
+        {
+            return Err(Status::permission_denied(PUBLISH_DENIED_MESSAGE));
+        }
 
         Ok(Response::new(
             self.1
-                .subscribers
-                .network()
-                .channel()
-                .publish_to_scope(&channel, request)
-                .await,
+                .channels
+                .publish(self.1.subscribers.network().channel(), request)
+                .await?,
         ))
     }
     async fn subscribe_to_channel(
         &self,
-        request: Request<String>,
+        request: Request<SubscribeReq>,
     ) -> Result<Response<Self::SubscribeToChannelStream>, Status> {
+        let auth = TonicTask::get_auth(AuthType::Server, &request)?;
+        let request = request.into_inner();
+        check_name(&request.channel)?;
+        if !auth
+            .get_server()
+            .is_some_and(|server| server.channels().can_subscribe(&request.channel))
+        {
+            return Err(Status::permission_denied(SUBSCRIBE_DENIED_MESSAGE));
+        }
+
+        let start = match request.start {
+            Some(Start::Sequence(sequence)) => StartPoint::Sequence(sequence),
+            Some(Start::Timestamp(timestamp)) => StartPoint::Timestamp(timestamp),
+            None => StartPoint::Live,
+        };
+
+        Ok(Response::new(
+            self.1
+                .channels
+                .subscribe(
+                    self.1.subscribers.network().channel(),
+                    request.channel,
+                    &start,
+                    request.consumer.as_deref(),
+                )
+                .await?,
+        ))
+    }
+    async fn acknowledge_messages(&self, request: Request<AckReq>) -> Result<Response<()>, Status> {
+        let auth = TonicTask::get_auth(AuthType::Server, &request)?;
+        let request = request.into_inner();
+        check_name(&request.channel)?;
+        if !auth
+            .get_server()
+            .is_some_and(|server| server.channels().can_subscribe(&request.channel))
+        {
+            return Err(Status::permission_denied(SUBSCRIBE_DENIED_MESSAGE));
+        }
+
+        if self
+            .1
+            .channels
+            .acknowledge(&request.channel, &request.consumer, request.sequence)
+            .await
+        {
+            Ok(Response::new(()))
+        } else {
+            Err(Status::not_found("Consumer not found"))
+        }
+    }
+
+    async fn request_on_channel(
+        &self,
+        request: Request<ChannelRequestMsg>,
+    ) -> Result<Response<ChannelReplyMsg>, Status> {
+        let auth = TonicTask::get_auth(AuthType::Server, &request)?;
         let request = request.into_inner();
+        check_name(&request.channel)?;
+        if !auth
+            .get_server()
+            .is_some_and(|server| server.channels().can_publish(&request.channel))
+        {
+            return Err(Status::permission_denied(PUBLISH_DENIED_MESSAGE));
+        }
+
+        Ok(Response::new(
+            self.1
+                .channels
+                .request(self.1.subscribers.network().request(), request)
+                .await?,
+        ))
+    }
+    async fn subscribe_to_requests(
+        &self,
+        request: Request<String>,
+    ) -> Result<Response<Self::SubscribeToRequestsStream>, Status> {
+        let auth = TonicTask::get_auth(AuthType::Server, &request)?;
+        let request = request.into_inner();
+        check_name(&request)?;
+        if !auth
+            .get_server()
+            .is_some_and(|server| server.channels().can_subscribe(&request))
+        {
+            return Err(Status::permission_denied(SUBSCRIBE_DENIED_MESSAGE));
+        }
 
         let (sender, receiver) = Subscriber::create_network();
         self.1
             .subscribers
             .network()
-            .channel()
+            .request()
             .subscribe_to_scope(request, sender)
             .await;
 
         Ok(Response::new(receiver))
     }
+    async fn reply_to_request(
+        &self,
+        request: Request<ChannelReplyMsg>,
+    ) -> Result<Response<bool>, Status> {
+        let request = request.into_inner();
+
+        Ok(Response::new(self.1.channels.reply(request).await))
+    }
 
     // Server
     async fn get_server(
         &self,
         request: Request<String>,
-    ) -> Result<Response<common_server::Short>, Status> {
+    ) -> Result<Response<client::server::Detail>, Status> {
         Ok(Response::new(
-            TonicTask::execute::<common_server::Short, _, _>(
+            TonicTask::execute::<client::server::Detail, _, _>(
                 AuthType::Server,
                 &self.0,
                 request,
@@ -319,9 +538,9 @@ impl ClientService for ClientServiceImpl {
     async fn get_server_from_name(
         &self,
         request: Request<String>,
-    ) -> Result<Response<common_server::Short>, Status> {
+    ) -> Result<Response<client::server::Detail>, Status> {
         Ok(Response::new(
-            TonicTask::execute::<common_server::Short, _, _>(
+            TonicTask::execute::<client::server::Detail, _, _>(
                 AuthType::Server,
                 &self.0,
                 request,
@@ -336,14 +555,24 @@ impl ClientService for ClientServiceImpl {
     }
     async fn get_servers(
         &self,
-        request: Request<()>,
+        request: Request<common_server::ListReq>,
     ) -> Result<Response<common_server::List>, Status> {
         Ok(Response::new(
             TonicTask::execute::<common_server::List, _, _>(
                 AuthType::Server,
                 &self.0,
                 request,
-                |_, _| Ok(Box::new(GetServersTask)),
+                |request, _| {
+                    let protocol = Protocol::of(&request);
+                    let request = request.into_inner();
+
+                    let selector = ServerSelector::try_from(&request)?;
+
+                    Ok(Box::new(GetServersTask(
+                        selector,
+                        Pagination::new(request.page, protocol),
+                    )))
+                },
             )
             .await?,
         ))
@@ -353,9 +582,9 @@ impl ClientService for ClientServiceImpl {
     async fn get_group(
         &self,
         request: Request<String>,
-    ) -> Result<Response<common_group::Short>, Status> {
+    ) -> Result<Response<client::group::Detail>, Status> {
         Ok(Response::new(
-            TonicTask::execute::<common_group::Short, _, _>(
+            TonicTask::execute::<client::group::Detail, _, _>(
                 AuthType::Server,
                 &self.0,
                 request,
@@ -370,20 +599,26 @@ impl ClientService for ClientServiceImpl {
     }
     async fn get_groups(
         &self,
-        request: Request<()>,
+        request: Request<common_group::ListReq>,
     ) -> Result<Response<common_group::List>, Status> {
         Ok(Response::new(
             TonicTask::execute::<common_group::List, _, _>(
                 AuthType::Server,
                 &self.0,
                 request,
-                |_, _| Ok(Box::new(GetGroupsTask)),
+                |request, _| Ok(Box::new(GetGroupsTask::from(request))),
             )
             .await?,
         ))
     }
 
     // Version info
+    async fn handshake(
+        &self,
+        request: Request<handshake::Req>,
+    ) -> Result<Response<handshake::Res>, Status> {
+        Ok(Response::new(negotiate(request.get_ref())))
+    }
     async fn get_proto_ver(&self, _request: Request<()>) -> Result<Response<u32>, Status> {
         Ok(Response::new(VERSION.protocol))
     }
@@ -411,3 +646,50 @@ impl ClientService for ClientServiceImpl {
         Ok(Response::new(receiver))
     }
 }
+
+#[allow(clippy::result_large_err)]
+fn transfer_task(
+    request: TransferReq,
+    auth: Authorization,
+    reporter: TransferReporter,
+) -> Result<BoxedTask, Status> {
+    let target = match request.target {
+        Some(target) => match Type::try_from(target.r#type) {
+            Ok(r#type) => match (target.target, r#type) {
+                (Some(target), Type::Group) => TransferTarget::Group(target),
+                (Some(target), Type::Server) => {
+                    TransferTarget::Server(match Uuid::from_str(&target) {
+                        Ok(uuid) => uuid,
+                        Err(_) => {
+                            return Err(Status::invalid_argument("Invalid UUID provided"));
+                        }
+                    })
+                }
+                (None, Type::Fallback) => TransferTarget::Fallback,
+                _ => {
+                    return Err(Status::invalid_argument("Invalid target type combination"));
+                }
+            },
+            Err(_) => {
+                return Err(Status::invalid_argument("Invalid target type provided"));
+            }
+        },
+        None => return Err(Status::invalid_argument("Missing target")),
+    };
+    let uuids = request
+        .ids
+        .into_iter()
+        .map(|id| match Uuid::from_str(&id) {
+            Ok(uuid) => Ok(uuid),
+            Err(_) => Err(Status::invalid_argument("Invalid UUID provided")),
+        })
+        .collect::<Result<Vec<Uuid>, _>>()?;
+
+    Ok(Box::new(TransferUsersTask(
+        auth,
+        uuids,
+        target,
+        request.together,
+        reporter,
+    )))
+}
@@ -2,13 +2,12 @@ use anyhow::Result;
 use tonic::{Status, async_trait};
 
 use crate::{
-    application::{Controller, group::Group},
-    network::proto::common::common_group::{List, Short},
+    application::{Controller, server::selector::ServerSelector},
+    network::proto::client::{group::Detail, server},
     task::{BoxedAny, GenericTask, network::TonicTask},
 };
 
 pub struct GetGroupTask(pub String);
-pub struct GetGroupsTask;
 
 #[async_trait]
 impl GenericTask for GetGroupTask {
@@ -17,28 +16,18 @@ impl GenericTask for GetGroupTask {
             return TonicTask::new_err(Status::not_found("Group not found"));
         };
 
-        TonicTask::new_ok(Short::from(&group))
-    }
-}
+        let servers = controller
+            .servers
+            .select_servers(&ServerSelector::group(group.name().clone()))
+            .into_iter()
+            .map(server::Detail::from)
+            .collect::<Vec<_>>();
 
-#[async_trait]
-impl GenericTask for GetGroupsTask {
-    async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
-        TonicTask::new_ok(List {
-            groups: controller
-                .groups
-                .get_groups()
-                .iter()
-                .map(Into::into)
-                .collect(),
-        })
-    }
-}
-
-impl From<&&Group> for Short {
-    fn from(group: &&Group) -> Self {
-        Self {
+        TonicTask::new_ok(Detail {
             name: group.name().clone(),
-        }
+            users: servers.iter().map(|server| server.users).sum(),
+            max_players: servers.iter().map(|server| server.max_players).sum(),
+            servers,
+        })
     }
 }
@@ -3,14 +3,26 @@ use tonic::{Status, async_trait};
 use uuid::Uuid;
 
 use crate::{
-    application::{Controller, server::Server},
-    network::proto::common::common_server::{List, Short},
+    application::{
+        Controller,
+        server::{Server, selector::ServerSelector},
+    },
+    network::{
+        page::{Pagination, id_key},
+        proto::{
+            client::server::Detail,
+            common::{
+                Address,
+                common_server::{self, List, Short},
+            },
+        },
+    },
     task::{BoxedAny, GenericTask, network::TonicTask},
 };
 
 pub struct GetServerTask(pub Uuid);
 pub struct GetServerFromNameTask(pub String);
-pub struct GetServersTask;
+pub struct GetServersTask(pub ServerSelector, pub Pagination);
 
 #[async_trait]
 impl GenericTask for GetServerTask {
@@ -19,7 +31,7 @@ impl GenericTask for GetServerTask {
             return TonicTask::new_err(Status::not_found("Server not found"));
         };
 
-        TonicTask::new_ok(Short::from(&server))
+        TonicTask::new_ok(Detail::from(server))
     }
 }
 
@@ -30,20 +42,22 @@ impl GenericTask for GetServerFromNameTask {
             return TonicTask::new_err(Status::not_found("Server not found"));
         };
 
-        TonicTask::new_ok(Short::from(&server))
+        TonicTask::new_ok(Detail::from(server))
     }
 }
 
 #[async_trait]
 impl GenericTask for GetServersTask {
     async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
+        let (servers, next) = self
+            .1
+            .paginate(controller.servers.select_servers(&self.0), |server| {
+                id_key(server.id())
+            });
+
         TonicTask::new_ok(List {
-            servers: controller
-                .servers
-                .get_servers()
-                .iter()
-                .map(Into::into)
-                .collect(),
+            servers: servers.iter().map(Into::into).collect(),
+            next,
         })
     }
 }
@@ -58,3 +72,28 @@ impl From<&&Server> for Short {
         }
     }
 }
+
+impl From<&Server> for Detail {
+    fn from(server: &Server) -> Self {
+        Self {
+            name: server.id().name().clone(),
+            id: server.id().uuid().to_string(),
+            group: server.group().clone(),
+            node: server.node().clone(),
+            addresses: server
+                .allocation()
+                .ports()
+                .iter()
+                .map(|port| Address {
+                    host: port.host.clone(),
+                    port: u32::from(port.port),
+                })
+                .collect(),
+            ready: *server.ready(),
+            users: *server.connected_users(),
+            max_players: *server.allocation().specification().max_players(),
+            state: common_server::State::from(server.state()) as i32,
+            reserved: *server.reserved(),
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use anyhow::Result;
 use tonic::{Status, async_trait};
 use uuid::Uuid;
@@ -6,10 +8,16 @@ use crate::{
     application::{
         Controller,
         auth::{ActionResult, Authorization},
-        server::NameAndUuid,
-        user::CurrentServer,
+        server::{NameAndUuid, State},
+        user::{MAX_ENTRIES, MAX_NAMESPACES, UserData},
+    },
+    network::proto::{
+        client::{
+            server::Detail,
+            user::{Data, Namespace},
+        },
+        common::{KeyValue, common_user::Item},
     },
-    network::proto::common::common_user::{Item, List},
     task::{BoxedAny, GenericTask, network::TonicTask},
 };
 
@@ -17,8 +25,15 @@ pub struct UserConnectedTask(pub Authorization, pub NameAndUuid);
 pub struct UserDisconnectedTask(pub Authorization, pub Uuid);
 pub struct GetUserTask(pub Uuid);
 pub struct GetUserFromNameTask(pub String);
-pub struct GetUsersTask;
 pub struct UserCountTask;
+pub struct SetUserDataTask(
+    pub Uuid,
+    pub String,
+    pub Vec<(String, String)>,
+    pub Vec<String>,
+);
+pub struct GetUserDataTask(pub Uuid, pub Option<String>);
+pub struct ResolveReconnectTask(pub Authorization, pub Uuid);
 
 #[async_trait]
 impl GenericTask for UserConnectedTask {
@@ -30,7 +45,14 @@ impl GenericTask for UserConnectedTask {
         else {
             return TonicTask::new_link_error();
         };
-        controller.users.user_connected(server, self.1.clone());
+        if let Some(target) = controller
+            .users
+            .user_connected(server, self.1.clone(), &controller.shared)
+            .await
+            && let Some(target) = controller.servers.get_server_mut(&target)
+        {
+            target.release_slot();
+        }
         TonicTask::new_empty()
     }
 }
@@ -45,7 +67,12 @@ impl GenericTask for UserDisconnectedTask {
         else {
             return TonicTask::new_link_error();
         };
-        if controller.users.user_disconnected(server, &self.1) == ActionResult::Denied {
+        if controller
+            .users
+            .user_disconnected(server, &self.1, &controller.shared)
+            .await
+            == ActionResult::Denied
+        {
             return TonicTask::new_permission_error("You are not allowed to disconnect this user");
         }
         TonicTask::new_empty()
@@ -59,15 +86,7 @@ impl GenericTask for GetUserTask {
             return TonicTask::new_err(Status::not_found("User not found"));
         };
 
-        TonicTask::new_ok(Item {
-            name: user.id().name().clone(),
-            id: user.id().uuid().to_string(),
-            server: if let CurrentServer::Connected(server) = user.server() {
-                Some(server.uuid().to_string())
-            } else {
-                None
-            },
-        })
+        TonicTask::new_ok(Item::from(user))
     }
 }
 
@@ -78,43 +97,108 @@ impl GenericTask for GetUserFromNameTask {
             return TonicTask::new_err(Status::not_found("User not found"));
         };
 
-        TonicTask::new_ok(Item {
-            name: user.id().name().clone(),
-            id: user.id().uuid().to_string(),
-            server: if let CurrentServer::Connected(server) = user.server() {
-                Some(server.uuid().to_string())
-            } else {
-                None
-            },
-        })
+        TonicTask::new_ok(Item::from(user))
     }
 }
 
 #[async_trait]
-impl GenericTask for GetUsersTask {
+impl GenericTask for UserCountTask {
     async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
-        TonicTask::new_ok(List {
-            users: controller
-                .users
-                .get_users()
+        TonicTask::new_ok(controller.users.get_user_count())
+    }
+}
+
+#[async_trait]
+impl GenericTask for SetUserDataTask {
+    async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
+        let Some(user) = controller.users.get_user_mut(&self.0) else {
+            return TonicTask::new_err(Status::not_found("User not found"));
+        };
+
+        let data = user.data_mut();
+        if !data.fits(&self.1, &self.2, &self.3) {
+            return TonicTask::new_err(Status::resource_exhausted(format!(
+                "User data is limited to {MAX_NAMESPACES} namespaces with {MAX_ENTRIES} entries each"
+            )));
+        }
+        for (key, value) in self.2.drain(..) {
+            data.set(&self.1, key, value);
+        }
+        for key in &self.3 {
+            data.remove(&self.1, key);
+        }
+        TonicTask::new_empty()
+    }
+}
+
+#[async_trait]
+impl GenericTask for GetUserDataTask {
+    async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
+        let Some(user) = controller.users.get_user(&self.0) else {
+            return TonicTask::new_err(Status::not_found("User not found"));
+        };
+
+        let namespaces = match &self.1 {
+            Some(namespace) => user
+                .data()
+                .get_namespace(namespace)
+                .map_or_else(Vec::new, |entries| {
+                    vec![Namespace::from((namespace, entries))]
+                }),
+            None => user.data().into(),
+        };
+        TonicTask::new_ok(Data { namespaces })
+    }
+}
+
+impl From<(&String, &HashMap<String, String>)> for Namespace {
+    fn from((name, entries): (&String, &HashMap<String, String>)) -> Self {
+        Self {
+            name: name.clone(),
+            entries: entries
                 .iter()
-                .map(|user| Item {
-                    name: user.id().name().clone(),
-                    id: user.id().uuid().to_string(),
-                    server: if let CurrentServer::Connected(server) = user.server() {
-                        Some(server.uuid().to_string())
-                    } else {
-                        None
-                    },
+                .map(|(key, value)| KeyValue {
+                    key: key.clone(),
+                    value: value.clone(),
                 })
                 .collect(),
-        })
+        }
+    }
+}
+
+impl From<&UserData> for Vec<Namespace> {
+    fn from(data: &UserData) -> Self {
+        data.get_namespaces().iter().map(Into::into).collect()
     }
 }
 
 #[async_trait]
-impl GenericTask for UserCountTask {
+impl GenericTask for ResolveReconnectTask {
     async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
-        TonicTask::new_ok(controller.users.get_user_count())
+        let Some(current) = self.0.get_server() else {
+            return TonicTask::new_link_error();
+        };
+        let session = controller.users.get_last_session(&self.1);
+
+        // Prefer the previous server, then its group and finally any fallback server
+        let server = session
+            .and_then(|session| controller.servers.get_server(session.server().uuid()))
+            .filter(|server| *server.ready() && *server.state() == State::Running)
+            .or_else(|| {
+                session
+                    .and_then(|session| session.group().as_ref())
+                    .and_then(|group| controller.groups.get_group(group))
+                    .and_then(|group| group.find_free_server(&controller.servers, 1))
+            })
+            .or_else(|| {
+                controller
+                    .servers
+                    .find_fallback_server(&[*current.uuid()], 1)
+            });
+        let Some(server) = server else {
+            return TonicTask::new_err(Status::unavailable("No server available to reconnect to"));
+        };
+
+        TonicTask::new_ok(Detail::from(server))
     }
 }
@@ -0,0 +1,53 @@
+use anyhow::Result;
+use tonic::{Request, async_trait};
+
+use crate::{
+    application::{Controller, group::Group},
+    network::{
+        handshake::Protocol,
+        page::Pagination,
+        proto::common::common_group::{List, ListReq, Short},
+    },
+    task::{BoxedAny, GenericTask, network::TonicTask},
+};
+
+// Both services list groups the same way
+pub struct GetGroupsTask(pub Option<String>, pub Pagination);
+
+impl From<Request<ListReq>> for GetGroupsTask {
+    fn from(request: Request<ListReq>) -> Self {
+        let protocol = Protocol::of(&request);
+        let value = request.into_inner();
+        Self(value.node, Pagination::new(value.page, protocol))
+    }
+}
+
+#[async_trait]
+impl GenericTask for GetGroupsTask {
+    async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
+        let groups = controller
+            .groups
+            .get_groups()
+            .into_iter()
+            .filter(|group| {
+                self.0
+                    .as_ref()
+                    .is_none_or(|node| group.nodes().contains(node))
+            })
+            .collect();
+        let (groups, next) = self.1.paginate(groups, |group| group.name().clone());
+
+        TonicTask::new_ok(List {
+            groups: groups.into_iter().map(Into::into).collect(),
+            next,
+        })
+    }
+}
+
+impl From<&Group> for Short {
+    fn from(group: &Group) -> Self {
+        Self {
+            name: group.name().clone(),
+        }
+    }
+}
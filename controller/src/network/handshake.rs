@@ -0,0 +1,92 @@
+use tonic::{Request, Status, metadata::MetadataMap};
+
+use crate::VERSION;
+
+use super::proto::common::handshake::{Req, Res};
+
+// Oldest protocol version that is still accepted. We keep at least one previous version working
+pub const MIN_PROTOCOL_VERSION: u32 = VERSION.protocol - 1;
+
+// Clients send their own protocol version with every request. Clients that predate the handshake do not
+const PROTOCOL_HEADER: &str = "protocol-version";
+
+// Version 14 added the handshake, a default page size for list RPCs and explicit bulk selectors
+const HANDSHAKE_VERSION: u32 = 14;
+
+// Features that clients can check for instead of comparing protocol versions
+const CAPABILITIES: &[&str] = &[
+    "pagination",
+    "bulk-power",
+    "user-data",
+    "channel-history",
+    "channel-requests",
+    "transfer-queue",
+    "sticky-sessions",
+    "party-transfers",
+    "slot-reservations",
+    "actions",
+    "user-history",
+    "log-archive",
+    "screen-options",
+    "screen-alerts",
+    "bulk-screen-write",
+    "screen-streams",
+    "plugin-reload",
+    "plugin-config",
+    "transfer-status",
+];
+
+pub fn negotiate(request: &Req) -> Res {
+    Res {
+        min: MIN_PROTOCOL_VERSION,
+        max: VERSION.protocol,
+        protocol: (request.protocol >= MIN_PROTOCOL_VERSION)
+            .then(|| request.protocol.min(VERSION.protocol)),
+        capabilities: CAPABILITIES
+            .iter()
+            .map(|capability| (*capability).to_string())
+            .collect(),
+    }
+}
+
+// Protocol version a request is handled with. Attached to every request by the auth interceptor
+#[derive(Clone, Copy)]
+pub struct Protocol(u32);
+
+impl Protocol {
+    pub fn of<T>(request: &Request<T>) -> Self {
+        request
+            .extensions()
+            .get::<Self>()
+            .copied()
+            .unwrap_or(Self(HANDSHAKE_VERSION - 1))
+    }
+
+    pub fn from_metadata(metadata: &MetadataMap) -> Result<Self, Status> {
+        let Some(value) = metadata.get(PROTOCOL_HEADER) else {
+            return Ok(Self(HANDSHAKE_VERSION - 1));
+        };
+        let version = value
+            .to_str()
+            .ok()
+            .and_then(|value| value.parse::<u32>().ok())
+            .ok_or_else(|| Status::invalid_argument("Invalid protocol version provided"))?;
+        Ok(Self(version.min(VERSION.protocol)))
+    }
+
+    // The handshake itself stays available so incompatible clients can find out why
+    pub fn check(self) -> Result<(), Status> {
+        if self.0 < MIN_PROTOCOL_VERSION {
+            return Err(Status::failed_precondition(format!(
+                "Protocol version {} is no longer supported. The oldest supported version is {}",
+                self.0, MIN_PROTOCOL_VERSION
+            )));
+        }
+        Ok(())
+    }
+
+    // Older clients rely on unlimited pages and on empty bulk selectors matching every server
+    pub fn has_limits(self) -> bool {
+        self.0 >= HANDSHAKE_VERSION
+    }
+}
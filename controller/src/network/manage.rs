@@ -1,65 +1,117 @@
-use std::{str::FromStr, sync::Arc};
+use std::{str::FromStr, sync::Arc, time::Duration};
 
+use action::{BroadcastMessageTask, KickUserTask, has_control_characters};
 use anyhow::Result;
-use group::{CreateGroupTask, GetGroupTask, GetGroupsTask, UpdateGroupTask};
+use group::{CreateGroupTask, GetGroupTask, UpdateGroupTask};
+use history::{player_stats, query_history, user_sessions};
 use node::{CreateNodeTask, GetNodeTask, GetNodesTask, UpdateNodeTask};
-use plugin::GetPluginsTask;
+use plugin::{
+    GetPluginConfigTask, GetPluginsTask, LoadPluginTask, ReloadPluginTask, UnloadPluginTask,
+    UpdatePluginConfigTask, ValidatePluginConfigTask, wait_for_config, wait_for_plugin,
+};
 use power::RequestStopTask;
+use regex::Regex;
 use resource::{DeleteResourceTask, SetResourceTask};
-use server::{GetServerFromNameTask, GetServerTask, GetServersTask, ScheduleServerTask};
-use tokio_stream::wrappers::ReceiverStream;
+use screen::SubscribeToScreensTask;
+use server::{
+    GetServerFromNameTask, GetServerTask, GetServersTask, RestartServersTask, ScheduleServerTask,
+    SelectServersTask, StopServersTask, bulk_selector,
+};
+use tokio::spawn;
+use tokio_stream::{
+    StreamExt,
+    wrappers::{ReceiverStream, UnboundedReceiverStream},
+};
 use tonic::{Request, Response, Status, async_trait};
 use transfer::TransferUsersTask;
-use user::{GetUserFromNameTask, GetUserTask, GetUsersTask, UserCountTask};
+use user::{GetUserFromNameTask, GetUserTask, UserCountTask};
 use uuid::Uuid;
 
 use crate::{
     VERSION,
     application::{
         Shared,
-        auth::{AuthType, permissions::Permissions},
+        auth::{AuthType, Authorization, permissions::Permissions},
+        channel::{ChannelAccess, StartPoint},
         group::{ScalingPolicy, StartConstraints},
         node::Capabilities,
-        server::{DiskRetention, FallbackPolicy, Resources, Specification},
+        plugin::{ConfigCheckHandle, manager::PluginLoadReceiver},
+        server::{
+            DiskRetention, FallbackPolicy, NameAndUuid, Resources, Specification,
+            screen::subscription::{
+                DEFAULT_SCROLLBACK, ScreenFilter, ScreenStart, ScreenSubscription,
+            },
+            selector::ServerSelector,
+        },
         subscriber::Subscriber,
-        user::transfer::TransferTarget,
+        user::transfer::{TransferReporter, TransferTarget},
     },
     task::{
+        BoxedTask,
         manager::TaskSender,
         network::{INSUFFICIENT_PERMISSIONS_MESSAGE, TonicTask},
     },
 };
 
-use super::proto::{
-    common::{
-        common_group, common_server, common_user,
-        notify::{PowerEvent, ReadyEvent},
-    },
-    manage::{
-        self,
-        manage_service_server::ManageService,
-        resource::{Category, DelReq, SetReq},
-        screen::{Lines, WriteReq},
-        transfer::{TransferReq, target::Type},
+use super::{
+    client::TransferStatusMsg,
+    group::GetGroupsTask,
+    handshake::{Protocol, negotiate},
+    page::Pagination,
+    proto::{
+        common::{
+            common_group, common_server, common_user, handshake,
+            notify::{PowerEvent, ReadyEvent},
+        },
+        manage::{
+            self,
+            action::{BroadcastReq, KickReq},
+            channel::TapReq,
+            history::{Sessions, SessionsReq, Stats, StatsReq},
+            manage_service_server::ManageService,
+            resource::{Category, DelReq, SetReq},
+            screen::{
+                Alert, BulkWriteReq, BulkWriteRes, Line, Lines, Logs, LogsReq, MultiSubscribeReq,
+                SubscribeReq, TaggedLines, WriteReq, bulk_write_res, line, subscribe_req,
+            },
+            server::BulkReq,
+            transfer::{TransferReq, target::Type},
+        },
     },
+    user::GetUsersTask,
 };
 
+mod action;
+mod channel;
 mod group;
+mod history;
 mod node;
 mod plugin;
 mod power;
 mod resource;
+mod screen;
 mod server;
 pub mod transfer;
 mod user;
 
+const DEFAULT_LOG_LINES: usize = 1000;
+const MAX_LOG_LINES: usize = 10000;
+
 pub type ScreenLines = Lines;
+pub type ScreenLine = Line;
+pub type ScreenLineHighlight = line::Highlight;
+pub type TaggedScreenLines = TaggedLines;
+pub type AlertMsg = Alert;
 
 pub struct ManageServiceImpl(pub TaskSender, pub Arc<Shared>);
 
 #[async_trait]
 impl ManageService for ManageServiceImpl {
     type SubscribeToScreenStream = ReceiverStream<Result<Lines, Status>>;
+    type SubscribeToScreensStream = ReceiverStream<Result<TaggedLines, Status>>;
+    type SubscribeToAlertsStream = ReceiverStream<Result<Alert, Status>>;
+    type TransferUsersWithStatusStream = UnboundedReceiverStream<Result<TransferStatusMsg, Status>>;
+    type TapChannelStream = ReceiverStream<Result<manage::channel::Msg, Status>>;
     type SubscribeToPowerEventsStream = ReceiverStream<Result<PowerEvent, Status>>;
     type SubscribeToReadyEventsStream = ReceiverStream<Result<ReadyEvent, Status>>;
 
@@ -139,6 +191,107 @@ impl ManageService for ManageServiceImpl {
             .await?,
         ))
     }
+    async fn load_plugin(&self, request: Request<String>) -> Result<Response<()>, Status> {
+        wait_for_plugin(
+            TonicTask::execute_authorized::<PluginLoadReceiver, _, _>(
+                AuthType::User,
+                Permissions::MANAGE_PLUGINS,
+                &self.0,
+                request,
+                |request, _| Ok(Box::new(LoadPluginTask(request.into_inner()))),
+            )
+            .await?,
+        )
+        .await?;
+        Ok(Response::new(()))
+    }
+    async fn unload_plugin(&self, request: Request<String>) -> Result<Response<()>, Status> {
+        Ok(Response::new(
+            TonicTask::execute_authorized::<(), _, _>(
+                AuthType::User,
+                Permissions::MANAGE_PLUGINS,
+                &self.0,
+                request,
+                |request, _| Ok(Box::new(UnloadPluginTask(request.into_inner()))),
+            )
+            .await?,
+        ))
+    }
+    async fn reload_plugin(&self, request: Request<String>) -> Result<Response<()>, Status> {
+        wait_for_plugin(
+            TonicTask::execute_authorized::<PluginLoadReceiver, _, _>(
+                AuthType::User,
+                Permissions::MANAGE_PLUGINS,
+                &self.0,
+                request,
+                |request, _| Ok(Box::new(ReloadPluginTask(request.into_inner()))),
+            )
+            .await?,
+        )
+        .await?;
+        Ok(Response::new(()))
+    }
+    async fn get_plugin_config(
+        &self,
+        request: Request<String>,
+    ) -> Result<Response<manage::plugin::Config>, Status> {
+        Ok(Response::new(
+            TonicTask::execute_authorized::<manage::plugin::Config, _, _>(
+                AuthType::User,
+                Permissions::MANAGE_PLUGINS,
+                &self.0,
+                request,
+                |request, _| Ok(Box::new(GetPluginConfigTask(request.into_inner()))),
+            )
+            .await?,
+        ))
+    }
+    async fn validate_plugin_config(
+        &self,
+        request: Request<manage::plugin::ConfigReq>,
+    ) -> Result<Response<()>, Status> {
+        wait_for_config(
+            TonicTask::execute_authorized::<ConfigCheckHandle, _, _>(
+                AuthType::User,
+                Permissions::MANAGE_PLUGINS,
+                &self.0,
+                request,
+                |request, _| {
+                    let request = request.into_inner();
+                    Ok(Box::new(ValidatePluginConfigTask(
+                        request.plugin,
+                        request.content,
+                    )))
+                },
+            )
+            .await?,
+        )
+        .await?;
+        Ok(Response::new(()))
+    }
+    async fn update_plugin_config(
+        &self,
+        request: Request<manage::plugin::ConfigReq>,
+    ) -> Result<Response<()>, Status> {
+        wait_for_config(
+            TonicTask::execute_authorized::<ConfigCheckHandle, _, _>(
+                AuthType::User,
+                Permissions::MANAGE_PLUGINS,
+                &self.0,
+                request,
+                |request, _| {
+                    let request = request.into_inner();
+                    Ok(Box::new(UpdatePluginConfigTask(
+                        request.plugin,
+                        request.content,
+                    )))
+                },
+            )
+            .await?,
+        )
+        .await?;
+        Ok(Response::new(()))
+    }
 
     // Node
     async fn create_node(
@@ -341,6 +494,9 @@ impl ManageService for ManageServiceImpl {
                             } else {
                                 FallbackPolicy::default()
                             };
+                            let channels = specification
+                                .channels
+                                .map_or_else(ChannelAccess::default, ChannelAccess::from);
                             Specification::new(
                                 settings,
                                 environment,
@@ -348,6 +504,7 @@ impl ManageService for ManageServiceImpl {
                                 image,
                                 max_players,
                                 fallback,
+                                channels,
                             )
                         }
                         None => return Err(Status::invalid_argument("No specification provided")),
@@ -442,6 +599,9 @@ impl ManageService for ManageServiceImpl {
                             } else {
                                 FallbackPolicy::default()
                             };
+                            let channels = specification
+                                .channels
+                                .map_or_else(ChannelAccess::default, ChannelAccess::from);
                             Some(Specification::new(
                                 settings,
                                 environment,
@@ -449,6 +609,7 @@ impl ManageService for ManageServiceImpl {
                                 image,
                                 max_players,
                                 fallback,
+                                channels,
                             ))
                         }
                         None => None,
@@ -484,7 +645,7 @@ impl ManageService for ManageServiceImpl {
     }
     async fn get_groups(
         &self,
-        request: Request<()>,
+        request: Request<common_group::ListReq>,
     ) -> Result<Response<common_group::List>, Status> {
         Ok(Response::new(
             TonicTask::execute_authorized::<common_group::List, _, _>(
@@ -492,7 +653,7 @@ impl ManageService for ManageServiceImpl {
                 Permissions::LIST,
                 &self.0,
                 request,
-                |_, _| Ok(Box::new(GetGroupsTask)),
+                |request, _| Ok(Box::new(GetGroupsTask::from(request))),
             )
             .await?,
         ))
@@ -560,6 +721,9 @@ impl ManageService for ManageServiceImpl {
                             } else {
                                 FallbackPolicy::default()
                             };
+                            let channels = specification
+                                .channels
+                                .map_or_else(ChannelAccess::default, ChannelAccess::from);
                             Specification::new(
                                 settings,
                                 environment,
@@ -567,6 +731,7 @@ impl ManageService for ManageServiceImpl {
                                 image,
                                 max_players,
                                 fallback,
+                                channels,
                             )
                         }
                         None => return Err(Status::invalid_argument("No spec provided")),
@@ -628,7 +793,7 @@ impl ManageService for ManageServiceImpl {
     }
     async fn get_servers(
         &self,
-        request: Request<()>,
+        request: Request<common_server::ListReq>,
     ) -> Result<Response<common_server::List>, Status> {
         Ok(Response::new(
             TonicTask::execute_authorized::<common_server::List, _, _>(
@@ -636,7 +801,67 @@ impl ManageService for ManageServiceImpl {
                 Permissions::LIST,
                 &self.0,
                 request,
-                |_, _| Ok(Box::new(GetServersTask)),
+                |request, _| {
+                    let protocol = Protocol::of(&request);
+                    let request = request.into_inner();
+
+                    let selector = ServerSelector::try_from(&request)?;
+
+                    Ok(Box::new(GetServersTask(
+                        selector,
+                        Pagination::new(request.page, protocol),
+                    )))
+                },
+            )
+            .await?,
+        ))
+    }
+    async fn restart_servers(
+        &self,
+        request: Request<BulkReq>,
+    ) -> Result<Response<common_server::List>, Status> {
+        Ok(Response::new(
+            TonicTask::execute_authorized::<common_server::List, _, _>(
+                AuthType::User,
+                Permissions::RESTART_SERVER,
+                &self.0,
+                request,
+                |request, _| {
+                    let protocol = Protocol::of(&request);
+                    let request = request.into_inner();
+
+                    let selector = bulk_selector(request.selector, protocol)?;
+                    let delay = request
+                        .delay
+                        .map(|delay| Duration::from_secs(u64::from(delay)));
+
+                    Ok(Box::new(RestartServersTask(selector, delay)))
+                },
+            )
+            .await?,
+        ))
+    }
+    async fn stop_servers(
+        &self,
+        request: Request<BulkReq>,
+    ) -> Result<Response<common_server::List>, Status> {
+        Ok(Response::new(
+            TonicTask::execute_authorized::<common_server::List, _, _>(
+                AuthType::User,
+                Permissions::STOP_SERVER,
+                &self.0,
+                request,
+                |request, _| {
+                    let protocol = Protocol::of(&request);
+                    let request = request.into_inner();
+
+                    let selector = bulk_selector(request.selector, protocol)?;
+                    let delay = request
+                        .delay
+                        .map(|delay| Duration::from_secs(u64::from(delay)));
+
+                    Ok(Box::new(StopServersTask(selector, delay)))
+                },
             )
             .await?,
         ))
@@ -660,9 +885,47 @@ impl ManageService for ManageServiceImpl {
             Ok(_) => Ok(Response::new(())),
         }
     }
+    async fn write_to_screens(
+        &self,
+        request: Request<BulkWriteReq>,
+    ) -> Result<Response<BulkWriteRes>, Status> {
+        let data = request.get_ref().data.clone();
+        let servers = TonicTask::execute_authorized::<Vec<NameAndUuid>, _, _>(
+            AuthType::User,
+            Permissions::WRITE_TO_SCREEN,
+            &self.0,
+            request,
+            |request, _| {
+                let protocol = Protocol::of(&request);
+                let request = request.into_inner();
+                let selector = bulk_selector(request.selector, protocol)?;
+
+                Ok(Box::new(SelectServersTask(selector)))
+            },
+        )
+        .await?;
+
+        let results = self
+            .1
+            .screens
+            .write_many(servers, &data)
+            .await
+            .into_iter()
+            .map(|(server, result)| {
+                let error = result.err().map(|error| error.message().to_string());
+                bulk_write_res::Result {
+                    server: server.uuid().to_string(),
+                    name: server.name().clone(),
+                    success: error.is_none(),
+                    error,
+                }
+            })
+            .collect();
+        Ok(Response::new(BulkWriteRes { results }))
+    }
     async fn subscribe_to_screen(
         &self,
-        request: Request<String>,
+        request: Request<SubscribeReq>,
     ) -> Result<Response<Self::SubscribeToScreenStream>, Status> {
         let auth = TonicTask::get_auth(AuthType::User, &request)?;
         if !auth.is_allowed(Permissions::READ_SCREEN) {
@@ -671,11 +934,107 @@ impl ManageService for ManageServiceImpl {
 
         let request = request.into_inner();
 
-        let Ok(uuid) = Uuid::from_str(&request) else {
+        let Ok(uuid) = Uuid::from_str(&request.id) else {
             return Err(Status::invalid_argument("Invalid UUID provided"));
         };
 
-        Ok(Response::new(self.1.screens.subscribe_screen(&uuid).await?))
+        let start = match request.start {
+            Some(subscribe_req::Start::Sequence(sequence)) => ScreenStart::Sequence(sequence),
+            Some(subscribe_req::Start::Timestamp(timestamp)) => ScreenStart::Timestamp(timestamp),
+            None => ScreenStart::Scrollback(
+                request
+                    .scrollback
+                    .map_or(DEFAULT_SCROLLBACK, |scrollback| scrollback as usize),
+            ),
+        };
+        let filter = match request.filter {
+            Some(filter) => match Regex::new(&filter) {
+                Ok(regex) => Some(ScreenFilter::new(regex, request.highlight)),
+                Err(error) => {
+                    return Err(Status::invalid_argument(format!(
+                        "Invalid filter provided: {error}"
+                    )));
+                }
+            },
+            None => None,
+        };
+
+        Ok(Response::new(
+            self.1
+                .screens
+                .subscribe_screen(&uuid, ScreenSubscription::new(start, filter))
+                .await?,
+        ))
+    }
+    async fn subscribe_to_screens(
+        &self,
+        request: Request<MultiSubscribeReq>,
+    ) -> Result<Response<Self::SubscribeToScreensStream>, Status> {
+        Ok(Response::new(
+            TonicTask::execute_authorized::<Self::SubscribeToScreensStream, _, _>(
+                AuthType::User,
+                Permissions::READ_SCREEN,
+                &self.0,
+                request,
+                |request, _| {
+                    let protocol = Protocol::of(&request);
+                    let request = request.into_inner();
+                    let selector = bulk_selector(request.selector, protocol)?;
+                    let scrollback = request
+                        .scrollback
+                        .map_or(DEFAULT_SCROLLBACK, |scrollback| scrollback as usize);
+                    let filter = match request.filter {
+                        Some(filter) => match Regex::new(&filter) {
+                            Ok(regex) => Some(ScreenFilter::new(regex, request.highlight)),
+                            Err(error) => {
+                                return Err(Status::invalid_argument(format!(
+                                    "Invalid filter provided: {error}"
+                                )));
+                            }
+                        },
+                        None => None,
+                    };
+
+                    Ok(Box::new(SubscribeToScreensTask(
+                        selector, scrollback, filter,
+                    )))
+                },
+            )
+            .await?,
+        ))
+    }
+    async fn get_server_logs(&self, request: Request<LogsReq>) -> Result<Response<Logs>, Status> {
+        let auth = TonicTask::get_auth(AuthType::User, &request)?;
+        if !auth.is_allowed(Permissions::READ_SCREEN) {
+            return Err(Status::permission_denied(INSUFFICIENT_PERMISSIONS_MESSAGE));
+        }
+
+        let request = request.into_inner();
+        let limit = request
+            .lines
+            .map_or(DEFAULT_LOG_LINES, |lines| lines as usize)
+            .min(MAX_LOG_LINES);
+
+        let logs = self.1.screens.read_logs(request.server, limit).await?;
+        Ok(Response::new(Logs {
+            server: logs.server.uuid().to_string(),
+            name: logs.server.name().clone(),
+            lines: logs.lines,
+        }))
+    }
+    async fn subscribe_to_alerts(
+        &self,
+        request: Request<()>,
+    ) -> Result<Response<Self::SubscribeToAlertsStream>, Status> {
+        let auth = TonicTask::get_auth(AuthType::User, &request)?;
+        if !auth.is_allowed(Permissions::READ_SCREEN) {
+            return Err(Status::permission_denied(INSUFFICIENT_PERMISSIONS_MESSAGE));
+        }
+
+        let (sender, receiver) = Subscriber::create_network();
+        self.1.subscribers.network().alert().subscribe(sender).await;
+
+        Ok(Response::new(receiver))
     }
 
     // User
@@ -721,14 +1080,17 @@ impl ManageService for ManageServiceImpl {
             .await?,
         ))
     }
-    async fn get_users(&self, request: Request<()>) -> Result<Response<common_user::List>, Status> {
+    async fn get_users(
+        &self,
+        request: Request<common_user::ListReq>,
+    ) -> Result<Response<common_user::List>, Status> {
         Ok(Response::new(
             TonicTask::execute_authorized::<common_user::List, _, _>(
                 AuthType::User,
                 Permissions::LIST,
                 &self.0,
                 request,
-                |_, _| Ok(Box::new(GetUsersTask)),
+                |request, _| Ok(Box::new(GetUsersTask::try_from(request)?)),
             )
             .await?,
         ))
@@ -745,6 +1107,56 @@ impl ManageService for ManageServiceImpl {
             .await?,
         ))
     }
+    async fn get_user_sessions(
+        &self,
+        request: Request<SessionsReq>,
+    ) -> Result<Response<Sessions>, Status> {
+        let auth = TonicTask::get_auth(AuthType::User, &request)?;
+        if !auth.is_allowed(Permissions::GET_USER) {
+            return Err(Status::permission_denied(INSUFFICIENT_PERMISSIONS_MESSAGE));
+        }
+
+        let request = request.into_inner();
+        let Ok(uuid) = Uuid::from_str(&request.user) else {
+            return Err(Status::invalid_argument("Invalid UUID provided"));
+        };
+
+        Ok(Response::new(
+            query_history(move |history| user_sessions(history, &uuid, request.since)).await?,
+        ))
+    }
+    async fn get_player_stats(
+        &self,
+        request: Request<StatsReq>,
+    ) -> Result<Response<Stats>, Status> {
+        let auth = TonicTask::get_auth(AuthType::User, &request)?;
+        if !auth.is_allowed(Permissions::GET_USER) {
+            return Err(Status::permission_denied(INSUFFICIENT_PERMISSIONS_MESSAGE));
+        }
+
+        let request = request.into_inner();
+        let user = match request.user {
+            Some(user) => match Uuid::from_str(&user) {
+                Ok(uuid) => Some(uuid),
+                Err(_) => {
+                    return Err(Status::invalid_argument("Invalid UUID provided"));
+                }
+            },
+            None => None,
+        };
+
+        Ok(Response::new(
+            query_history(move |history| {
+                Ok(player_stats(
+                    history,
+                    user,
+                    request.group.as_ref(),
+                    request.since,
+                ))
+            })
+            .await?,
+        ))
+    }
 
     // Transfer
     async fn transfer_users(&self, request: Request<TransferReq>) -> Result<Response<u32>, Status> {
@@ -755,54 +1167,156 @@ impl ManageService for ManageServiceImpl {
                 &self.0,
                 request,
                 |request, auth| {
+                    transfer_task(request.into_inner(), auth, TransferReporter::default())
+                },
+            )
+            .await?,
+        ))
+    }
+    async fn transfer_users_with_status(
+        &self,
+        request: Request<TransferReq>,
+    ) -> Result<Response<Self::TransferUsersWithStatusStream>, Status> {
+        let (reporter, receiver) = TransferReporter::create();
+
+        // The status stream has to be returned before the transfers start so that nothing waits for the caller
+        let queue = self.0.clone();
+        spawn(async move {
+            if let Err(error) = TonicTask::execute_authorized::<u32, _, _>(
+                AuthType::User,
+                Permissions::TRANSFER_USER,
+                &queue,
+                request,
+                |request, auth| transfer_task(request.into_inner(), auth, reporter.clone()),
+            )
+            .await
+            {
+                reporter.fail(error);
+            }
+        });
+        Ok(Response::new(receiver))
+    }
+
+    // Action
+    async fn kick_user(&self, request: Request<KickReq>) -> Result<Response<()>, Status> {
+        Ok(Response::new(
+            TonicTask::execute_authorized::<(), _, _>(
+                AuthType::User,
+                Permissions::KICK_USER,
+                &self.0,
+                request,
+                |request, _| {
                     let request = request.into_inner();
 
-                    let target = match request.target {
-                        Some(target) => match Type::try_from(target.r#type) {
-                            Ok(r#type) => match (target.target, r#type) {
-                                (Some(target), Type::Group) => TransferTarget::Group(target),
-                                (Some(target), Type::Server) => {
-                                    TransferTarget::Server(match Uuid::from_str(&target) {
-                                        Ok(uuid) => uuid,
-                                        Err(_) => {
-                                            return Err(Status::invalid_argument(
-                                                "Invalid UUID provided",
-                                            ));
-                                        }
-                                    })
-                                }
-                                (None, Type::Fallback) => TransferTarget::Fallback,
-                                _ => {
-                                    return Err(Status::invalid_argument(
-                                        "Invalid target type combination",
-                                    ));
-                                }
-                            },
-                            Err(_) => {
-                                return Err(Status::invalid_argument(
-                                    "Invalid target type provided",
-                                ));
-                            }
-                        },
-                        None => return Err(Status::invalid_argument("Missing target")),
+                    let Ok(uuid) = Uuid::from_str(&request.user) else {
+                        return Err(Status::invalid_argument("Invalid UUID provided"));
                     };
-                    let uuids = request
-                        .ids
-                        .into_iter()
-                        .map(|id| match Uuid::from_str(&id) {
-                            Ok(uuid) => Ok(uuid),
-                            Err(_) => Err(Status::invalid_argument("Invalid UUID provided")),
-                        })
-                        .collect::<Result<Vec<Uuid>, _>>()?;
-
-                    Ok(Box::new(TransferUsersTask(auth, uuids, target)))
+                    if request
+                        .reason
+                        .as_deref()
+                        .is_some_and(has_control_characters)
+                    {
+                        return Err(Status::invalid_argument(
+                            "The reason can not contain control characters",
+                        ));
+                    }
+
+                    Ok(Box::new(KickUserTask(uuid, request.reason)))
+                },
+            )
+            .await?,
+        ))
+    }
+    async fn broadcast_message(
+        &self,
+        request: Request<BroadcastReq>,
+    ) -> Result<Response<u32>, Status> {
+        Ok(Response::new(
+            TonicTask::execute_authorized::<u32, _, _>(
+                AuthType::User,
+                Permissions::BROADCAST,
+                &self.0,
+                request,
+                |request, _| {
+                    let request = request.into_inner();
+                    if has_control_characters(&request.message) {
+                        return Err(Status::invalid_argument(
+                            "The message can not contain control characters",
+                        ));
+                    }
+
+                    Ok(Box::new(BroadcastMessageTask(
+                        request.message,
+                        request.group,
+                    )))
                 },
             )
             .await?,
         ))
     }
 
+    // Channel
+    async fn publish_to_channel(
+        &self,
+        request: Request<manage::channel::Msg>,
+    ) -> Result<Response<u32>, Status> {
+        let auth = TonicTask::get_auth(AuthType::User, &request)?;
+        if !auth.is_allowed(Permissions::PUBLISH_CHANNEL) {
+            return Err(Status::permission_denied(INSUFFICIENT_PERMISSIONS_MESSAGE));
+        }
+
+        Ok(Response::new(
+            self.1
+                .channels
+                .publish(
+                    self.1.subscribers.network().channel(),
+                    request.into_inner().into(),
+                )
+                .await?,
+        ))
+    }
+    async fn tap_channel(
+        &self,
+        request: Request<TapReq>,
+    ) -> Result<Response<Self::TapChannelStream>, Status> {
+        let auth = TonicTask::get_auth(AuthType::User, &request)?;
+        if !auth.is_allowed(Permissions::TAP_CHANNEL) {
+            return Err(Status::permission_denied(INSUFFICIENT_PERMISSIONS_MESSAGE));
+        }
+        let request = request.into_inner();
+
+        let start = request
+            .timestamp
+            .map_or(StartPoint::Live, StartPoint::Timestamp);
+        let mut stream = self
+            .1
+            .channels
+            .subscribe(
+                self.1.subscribers.network().channel(),
+                request.channel,
+                &start,
+                None,
+            )
+            .await?;
+
+        let (sender, receiver) = Subscriber::create_network();
+        spawn(async move {
+            while let Some(message) = stream.next().await {
+                if !sender.send_network(message.map(Into::into)).await {
+                    return;
+                }
+            }
+        });
+        Ok(Response::new(receiver))
+    }
+
     // Version info
+    async fn handshake(
+        &self,
+        request: Request<handshake::Req>,
+    ) -> Result<Response<handshake::Res>, Status> {
+        Ok(Response::new(negotiate(request.get_ref())))
+    }
     async fn get_proto_ver(&self, _request: Request<()>) -> Result<Response<u32>, Status> {
         Ok(Response::new(VERSION.protocol))
     }
@@ -840,3 +1354,50 @@ impl ManageService for ManageServiceImpl {
         Ok(Response::new(receiver))
     }
 }
+
+#[allow(clippy::result_large_err)]
+fn transfer_task(
+    request: TransferReq,
+    auth: Authorization,
+    reporter: TransferReporter,
+) -> Result<BoxedTask, Status> {
+    let target = match request.target {
+        Some(target) => match Type::try_from(target.r#type) {
+            Ok(r#type) => match (target.target, r#type) {
+                (Some(target), Type::Group) => TransferTarget::Group(target),
+                (Some(target), Type::Server) => {
+                    TransferTarget::Server(match Uuid::from_str(&target) {
+                        Ok(uuid) => uuid,
+                        Err(_) => {
+                            return Err(Status::invalid_argument("Invalid UUID provided"));
+                        }
+                    })
+                }
+                (None, Type::Fallback) => TransferTarget::Fallback,
+                _ => {
+                    return Err(Status::invalid_argument("Invalid target type combination"));
+                }
+            },
+            Err(_) => {
+                return Err(Status::invalid_argument("Invalid target type provided"));
+            }
+        },
+        None => return Err(Status::invalid_argument("Missing target")),
+    };
+    let uuids = request
+        .ids
+        .into_iter()
+        .map(|id| match Uuid::from_str(&id) {
+            Ok(uuid) => Ok(uuid),
+            Err(_) => Err(Status::invalid_argument("Invalid UUID provided")),
+        })
+        .collect::<Result<Vec<Uuid>, _>>()?;
+
+    Ok(Box::new(TransferUsersTask(
+        auth,
+        uuids,
+        target,
+        request.together,
+        reporter,
+    )))
+}
@@ -0,0 +1,91 @@
+use anyhow::Result;
+use tonic::{Status, async_trait};
+use uuid::Uuid;
+
+use crate::{
+    application::{Controller, server::selector::ServerSelector, user::CurrentServer},
+    network::{
+        client::ActionMsg,
+        proto::client::action::{Broadcast, Kick, Kind},
+    },
+    task::{BoxedAny, GenericTask, network::TonicTask},
+};
+
+// Reasons and messages end up in a console command, so a line break would start a second command
+pub fn has_control_characters(value: &str) -> bool {
+    value.chars().any(char::is_control)
+}
+
+pub struct KickUserTask(pub Uuid, pub Option<String>);
+pub struct BroadcastMessageTask(pub String, pub Option<String>);
+
+#[async_trait]
+impl GenericTask for KickUserTask {
+    async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
+        let Some(user) = controller.users.get_user(&self.0) else {
+            return TonicTask::new_err(Status::not_found("User not found"));
+        };
+        let CurrentServer::Connected(server) = user.server() else {
+            return TonicTask::new_err(Status::unavailable("User is currently transferring"));
+        };
+
+        let action = ActionMsg {
+            kind: Some(Kind::Kick(Kick {
+                user: self.0.to_string(),
+                reason: self.1.clone(),
+            })),
+        };
+        if controller
+            .shared
+            .subscribers
+            .network()
+            .action()
+            .publish_to_scope(server.uuid(), action)
+            .await
+            == 0
+        {
+            return TonicTask::new_err(Status::unavailable(format!(
+                "Server {server} is not listening for actions"
+            )));
+        }
+
+        TonicTask::new_empty()
+    }
+}
+
+#[async_trait]
+impl GenericTask for BroadcastMessageTask {
+    async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
+        if let Some(group) = &self.1
+            && controller.groups.get_group(group).is_none()
+        {
+            return TonicTask::new_err(Status::not_found("Group not found"));
+        }
+
+        let selector = self
+            .1
+            .clone()
+            .map_or_else(ServerSelector::default, ServerSelector::group);
+        let servers = controller
+            .servers
+            .select_servers(&selector)
+            .into_iter()
+            .map(|server| *server.id().uuid())
+            .collect::<Vec<_>>();
+
+        let action = ActionMsg {
+            kind: Some(Kind::Broadcast(Broadcast {
+                message: self.0.clone(),
+            })),
+        };
+        TonicTask::new_ok(
+            controller
+                .shared
+                .subscribers
+                .network()
+                .action()
+                .try_publish_to_matching(|server| servers.contains(server), action)
+                .await,
+        )
+    }
+}
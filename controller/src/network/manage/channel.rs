@@ -0,0 +1,23 @@
+use crate::network::{client::ChannelMsg, proto::manage::channel};
+
+impl From<channel::Msg> for ChannelMsg {
+    fn from(value: channel::Msg) -> Self {
+        Self {
+            channel: value.channel,
+            data: value.data,
+            timestamp: value.timestamp,
+            sequence: value.sequence,
+        }
+    }
+}
+
+impl From<ChannelMsg> for channel::Msg {
+    fn from(value: ChannelMsg) -> Self {
+        Self {
+            channel: value.channel,
+            data: value.data,
+            timestamp: value.timestamp,
+            sequence: value.sequence,
+        }
+    }
+}
@@ -4,13 +4,14 @@ use tonic::{Status, async_trait};
 use crate::{
     application::{
         Controller,
+        channel::ChannelAccess,
         group::{Group, ScalingPolicy, StartConstraints},
         server::{FallbackPolicy, Resources, Specification},
     },
     network::proto::{
-        common::{KeyValue, common_group::List},
+        common::KeyValue,
         manage::{
-            group::{Constraints, Detail, Scaling},
+            group::{AlertCounter, Constraints, Detail, Scaling},
             server::{self, Fallback},
         },
     },
@@ -34,7 +35,6 @@ pub struct UpdateGroupTask(
     pub Option<Vec<String>>,
 );
 pub struct GetGroupTask(pub String);
-pub struct GetGroupsTask;
 
 #[async_trait]
 impl GenericTask for CreateGroupTask {
@@ -87,21 +87,20 @@ impl GenericTask for GetGroupTask {
             return TonicTask::new_err(Status::not_found("Group not found"));
         };
 
-        TonicTask::new_ok(Detail::from(group))
-    }
-}
-
-#[async_trait]
-impl GenericTask for GetGroupsTask {
-    async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
-        TonicTask::new_ok(List {
-            groups: controller
-                .groups
-                .get_groups()
-                .iter()
-                .map(Into::into)
-                .collect(),
-        })
+        let mut detail = Detail::from(group);
+        detail.alerts = controller
+            .shared
+            .screens
+            .alert_counters(group.name())
+            .await
+            .into_iter()
+            .map(|counter| AlertCounter {
+                rule: counter.rule,
+                matches: counter.matches,
+                restarts: counter.restarts,
+            })
+            .collect();
+        TonicTask::new_ok(detail)
     }
 }
 
@@ -114,6 +113,7 @@ impl From<&Group> for Detail {
             constraints: Some(value.constraints().into()),
             resources: Some(value.resources().into()),
             specification: Some(value.specification().into()),
+            alerts: vec![],
         }
     }
 }
@@ -174,10 +174,26 @@ impl From<&Specification> for server::Specification {
                 .collect(),
             retention: Some(value.disk_retention().clone() as i32),
             fallback: value.fallback().to_grpc(),
+            channels: Some(value.channels().into()),
         }
     }
 }
 
+impl From<&ChannelAccess> for server::Channels {
+    fn from(value: &ChannelAccess) -> Self {
+        Self {
+            publish: value.publish().clone(),
+            subscribe: value.subscribe().clone(),
+        }
+    }
+}
+
+impl From<server::Channels> for ChannelAccess {
+    fn from(value: server::Channels) -> Self {
+        Self::new(value.publish, value.subscribe)
+    }
+}
+
 impl FallbackPolicy {
     pub fn to_grpc(&self) -> Option<Fallback> {
         if *self.enabled() {
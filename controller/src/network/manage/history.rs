@@ -0,0 +1,113 @@
+use std::collections::{BTreeMap, HashSet};
+
+use tokio::task::spawn_blocking;
+use tonic::Status;
+use uuid::Uuid;
+
+use crate::{
+    application::user::history::{History, SessionRecord, unix_now},
+    network::proto::manage::history::{GroupStats, Session, Sessions, Stats},
+};
+
+// The history is read from disk, so queries never run on the controller loop
+pub async fn query_history<T, F>(query: F) -> Result<T, Status>
+where
+    T: Send + 'static,
+    F: FnOnce(&History) -> Result<T, Status> + Send + 'static,
+{
+    match spawn_blocking(move || History::read().map(|history| query(&history))).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(error)) => Err(Status::internal(error.to_string())),
+        Err(error) => Err(Status::internal(error.to_string())),
+    }
+}
+
+pub fn user_sessions(
+    history: &History,
+    user: &Uuid,
+    since: Option<u64>,
+) -> Result<Sessions, Status> {
+    let Some(history) = history.get_user(user) else {
+        return Err(Status::not_found("No history recorded for this user"));
+    };
+
+    let since = since.unwrap_or_default();
+    Ok(Sessions {
+        name: history.name().clone(),
+        sessions: history
+            .sessions()
+            .iter()
+            .filter(|session| session.disconnected().is_none_or(|time| time >= since))
+            .map(Into::into)
+            .collect(),
+    })
+}
+
+pub fn player_stats(
+    history: &History,
+    user: Option<Uuid>,
+    group: Option<&String>,
+    since: Option<u64>,
+) -> Stats {
+    let since = since.unwrap_or_default();
+    let now = unix_now();
+
+    let mut stats = Stats::default();
+    let mut users = HashSet::new();
+    let mut groups = BTreeMap::<&String, (GroupStats, HashSet<&Uuid>)>::new();
+    for (uuid, history) in history
+        .get_users()
+        .filter(|(uuid, _)| user.is_none_or(|user| user == **uuid))
+    {
+        for session in history.sessions().iter().filter(|session| {
+            session.disconnected().is_none_or(|time| time >= since)
+                && group.is_none_or(|group| session.group().as_ref() == Some(group))
+        }) {
+            let playtime = session.playtime(since, now);
+            stats.sessions += 1;
+            stats.playtime += playtime;
+            users.insert(uuid);
+
+            if let Some(group) = session.group() {
+                let (stats, users) = groups.entry(group).or_insert_with(|| {
+                    (
+                        GroupStats {
+                            group: group.clone(),
+                            ..Default::default()
+                        },
+                        HashSet::new(),
+                    )
+                });
+                stats.sessions += 1;
+                stats.playtime += playtime;
+                users.insert(uuid);
+            }
+        }
+    }
+
+    stats.users = u32::try_from(users.len()).unwrap_or(u32::MAX);
+    stats.groups = groups
+        .into_values()
+        .map(|(mut stats, users)| {
+            stats.users = u32::try_from(users.len()).unwrap_or(u32::MAX);
+            stats
+        })
+        .collect();
+    if let Some(peak) = history.get_peak(since) {
+        stats.peak_users = *peak.users();
+        stats.peak_timestamp = Some(*peak.timestamp());
+    }
+    stats
+}
+
+impl From<&SessionRecord> for Session {
+    fn from(value: &SessionRecord) -> Self {
+        Self {
+            server: value.server().clone(),
+            group: value.group().clone(),
+            connected: *value.connected(),
+            disconnected: *value.disconnected(),
+            transferred: *value.transferred(),
+        }
+    }
+}
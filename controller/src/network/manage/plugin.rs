@@ -1,13 +1,31 @@
+use std::{io::ErrorKind, path::PathBuf};
+
 use anyhow::Result;
-use tonic::async_trait;
+use tokio::{fs, spawn};
+use tonic::{Status, async_trait};
 
 use crate::{
-    application::Controller,
-    network::proto::manage::plugin::{List, Short},
+    application::{
+        Controller,
+        plugin::{
+            BoxedPlugin, ConfigCheckHandle,
+            config::{ConfigField, ConfigKind, ConfigSchema},
+            manager::PluginLoadReceiver,
+        },
+    },
+    network::proto::manage::plugin::{Config, Field, List, Schema, Short, field::Kind},
+    storage::Storage,
     task::{BoxedAny, GenericTask, network::TonicTask},
 };
 
 pub struct GetPluginsTask();
+pub struct LoadPluginTask(pub String);
+pub struct UnloadPluginTask(pub String);
+pub struct ReloadPluginTask(pub String);
+
+pub struct GetPluginConfigTask(pub String);
+pub struct ValidatePluginConfigTask(pub String, pub String);
+pub struct UpdatePluginConfigTask(pub String, pub String);
 
 #[async_trait]
 impl GenericTask for GetPluginsTask {
@@ -23,6 +41,146 @@ impl GenericTask for GetPluginsTask {
     }
 }
 
+#[async_trait]
+impl GenericTask for LoadPluginTask {
+    async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
+        if !Storage::is_valid_name(&self.0) {
+            return TonicTask::new_err(Status::invalid_argument("Invalid plugin name"));
+        }
+        if controller.plugins.get_plugin(&self.0).is_some() {
+            return TonicTask::new_err(Status::already_exists("Plugin is already loaded"));
+        }
+
+        TonicTask::new_ok(controller.plugins.load(&self.0))
+    }
+}
+
+#[async_trait]
+impl GenericTask for UnloadPluginTask {
+    async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
+        if let Err(error) = controller.plugins.unload(&self.0, &controller.nodes) {
+            return TonicTask::new_err(error.into());
+        }
+        TonicTask::new_empty()
+    }
+}
+
+#[async_trait]
+impl GenericTask for ReloadPluginTask {
+    async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
+        if controller.plugins.get_plugin(&self.0).is_none() {
+            return TonicTask::new_err(Status::not_found("Plugin not found"));
+        }
+        TonicTask::new_ok(controller.plugins.load(&self.0))
+    }
+}
+
+#[async_trait]
+impl GenericTask for GetPluginConfigTask {
+    async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
+        let (_, schema, path) = match find_config(controller, &self.0) {
+            Ok(config) => config,
+            Err(error) => return TonicTask::new_err(error),
+        };
+
+        let content = match fs::read_to_string(&path).await {
+            Ok(content) => content,
+            Err(error) if error.kind() == ErrorKind::NotFound => String::new(),
+            Err(error) => {
+                return TonicTask::new_err(Status::internal(format!(
+                    "Failed to read config file: {error}"
+                )));
+            }
+        };
+        TonicTask::new_ok(Config {
+            content,
+            schema: Some(schema.into()),
+        })
+    }
+}
+
+#[async_trait]
+impl GenericTask for ValidatePluginConfigTask {
+    async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
+        let (plugin, schema, _) = match find_config(controller, &self.0) {
+            Ok(config) => config,
+            Err(error) => return TonicTask::new_err(error),
+        };
+        if let Err(reason) = schema.check(&self.1) {
+            return TonicTask::new_err(Status::invalid_argument(reason));
+        }
+
+        TonicTask::new_ok(plugin.validate_config(&self.1))
+    }
+}
+
+#[async_trait]
+impl GenericTask for UpdatePluginConfigTask {
+    async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
+        let (plugin, schema, path) = match find_config(controller, &self.0) {
+            Ok(config) => config,
+            Err(error) => return TonicTask::new_err(error),
+        };
+        if let Err(reason) = schema.check(&self.1) {
+            return TonicTask::new_err(Status::invalid_argument(reason));
+        }
+
+        let changed = plugin.config_changed(&self.1);
+        let content = self.1.clone();
+        TonicTask::new_ok::<ConfigCheckHandle>(spawn(async move {
+            if let Err(reason) = changed.await?? {
+                return Ok(Err(reason));
+            }
+
+            // The file is only touched once the plugin accepted the content
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).await?;
+            }
+            fs::write(&path, content).await?;
+            Ok(Ok(()))
+        }))
+    }
+}
+
+fn find_config<'a>(
+    controller: &'a Controller,
+    name: &str,
+) -> Result<(&'a BoxedPlugin, &'a ConfigSchema, PathBuf), Status> {
+    let Some(plugin) = controller.plugins.get_plugin(name) else {
+        return Err(Status::not_found("Plugin not found"));
+    };
+    let Some(schema) = plugin.config_schema() else {
+        return Err(Status::failed_precondition(
+            "Plugin does not declare a config schema",
+        ));
+    };
+    let Some(path) = schema.path(name) else {
+        return Err(Status::internal("Plugin declared an invalid config file"));
+    };
+    Ok((plugin, schema, path))
+}
+
+// The plugin answers asynchronously, so the result arrives after the task finished
+pub async fn wait_for_config(handle: ConfigCheckHandle) -> Result<(), Status> {
+    match handle.await {
+        Ok(Ok(Ok(()))) => Ok(()),
+        Ok(Ok(Err(reason))) => Err(Status::invalid_argument(reason)),
+        Ok(Err(error)) => Err(Status::internal(error.to_string())),
+        Err(error) => Err(Status::internal(error.to_string())),
+    }
+}
+
+// Loading happens in the background, so the result arrives after the task finished
+pub async fn wait_for_plugin(receiver: PluginLoadReceiver) -> Result<(), Status> {
+    match receiver.await {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(error)) => Err(Status::internal(error)),
+        Err(_) => Err(Status::aborted(
+            "The controller stopped before the plugin was loaded",
+        )),
+    }
+}
+
 impl From<&&String> for Short {
     fn from(plugin: &&String) -> Self {
         Self {
@@ -30,3 +188,40 @@ impl From<&&String> for Short {
         }
     }
 }
+
+impl From<&ConfigSchema> for Schema {
+    fn from(schema: &ConfigSchema) -> Self {
+        Self {
+            file: schema.file().clone(),
+            fields: schema
+                .fields()
+                .iter()
+                .map(std::convert::Into::into)
+                .collect(),
+        }
+    }
+}
+
+impl From<&ConfigField> for Field {
+    fn from(field: &ConfigField) -> Self {
+        Self {
+            path: field.path().clone(),
+            kind: Kind::from(*field.kind()) as i32,
+            description: field.description().clone(),
+            required: *field.required(),
+        }
+    }
+}
+
+impl From<ConfigKind> for Kind {
+    fn from(kind: ConfigKind) -> Self {
+        match kind {
+            ConfigKind::String => Kind::String,
+            ConfigKind::Integer => Kind::Integer,
+            ConfigKind::Float => Kind::Float,
+            ConfigKind::Boolean => Kind::Boolean,
+            ConfigKind::Array => Kind::Array,
+            ConfigKind::Table => Kind::Table,
+        }
+    }
+}
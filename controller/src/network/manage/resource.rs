@@ -21,7 +21,7 @@ impl GenericTask for SetResourceTask {
                     .nodes
                     .get_node_mut(&self.1)
                     .ok_or(Status::not_found("Node not found"))?;
-                if let Err(error) = node.set_active(self.2).await {
+                if let Err(error) = node.set_active(self.2, &controller.shared).await {
                     return TonicTask::new_err(Status::internal(error.to_string()));
                 }
                 TonicTask::new_empty()
@@ -31,7 +31,10 @@ impl GenericTask for SetResourceTask {
                     .groups
                     .get_group_mut(&self.1)
                     .ok_or(Status::not_found("Group not found"))?;
-                if let Err(error) = group.set_active(self.2, &mut controller.servers).await {
+                if let Err(error) = group
+                    .set_active(self.2, &mut controller.servers, &controller.shared)
+                    .await
+                {
                     return TonicTask::new_err(Status::internal(error.to_string()));
                 }
                 TonicTask::new_empty()
@@ -0,0 +1,37 @@
+use anyhow::Result;
+use tonic::async_trait;
+
+use crate::{
+    application::{
+        Controller,
+        server::{
+            NameAndUuid,
+            screen::subscription::{ScreenFilter, ScreenStream},
+            selector::ServerSelector,
+        },
+    },
+    task::{BoxedAny, GenericTask, network::TonicTask},
+};
+
+pub struct SubscribeToScreensTask(pub ServerSelector, pub usize, pub Option<ScreenFilter>);
+
+#[async_trait]
+impl GenericTask for SubscribeToScreensTask {
+    async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
+        let (stream, receiver) = ScreenStream::new(self.0.clone(), self.1, self.2.clone());
+
+        let servers = controller
+            .servers
+            .select_servers(stream.selector())
+            .into_iter()
+            .map(|server| server.id().clone())
+            .collect::<Vec<NameAndUuid>>();
+        controller
+            .shared
+            .screens
+            .subscribe_screens(&servers, stream)
+            .await;
+
+        TonicTask::new_ok(receiver)
+    }
+}
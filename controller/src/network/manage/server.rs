@@ -1,5 +1,9 @@
+use std::{str::FromStr, time::Duration};
+
 use anyhow::Result;
+use regex::Regex;
 use simplelog::debug;
+use tokio::time::Instant;
 use tonic::{Status, async_trait};
 use uuid::Uuid;
 
@@ -7,11 +11,22 @@ use crate::{
     application::{
         Controller,
         node::Allocation,
-        server::{Resources, Server, Specification, manager::StartRequest},
+        server::{
+            NameAndUuid, Resources, Server, Specification, State,
+            manager::{RestartRequest, StartRequest, StopRequest},
+            selector::ServerSelector,
+        },
     },
-    network::proto::{
-        common::{Address, common_server::List},
-        manage::server::{self, Detail},
+    network::{
+        handshake::Protocol,
+        page::{Pagination, id_key},
+        proto::{
+            common::{
+                Address,
+                common_server::{self, List, Short},
+            },
+            manage::server::{self, Detail, Selector},
+        },
     },
     task::{BoxedAny, GenericTask, network::TonicTask},
 };
@@ -25,7 +40,10 @@ pub struct ScheduleServerTask(
 );
 pub struct GetServerTask(pub Uuid);
 pub struct GetServerFromNameTask(pub String);
-pub struct GetServersTask;
+pub struct GetServersTask(pub ServerSelector, pub Pagination);
+pub struct RestartServersTask(pub ServerSelector, pub Option<Duration>);
+pub struct StopServersTask(pub ServerSelector, pub Option<Duration>);
+pub struct SelectServersTask(pub ServerSelector);
 
 #[async_trait]
 impl GenericTask for ScheduleServerTask {
@@ -75,17 +93,147 @@ impl GenericTask for GetServerFromNameTask {
 #[async_trait]
 impl GenericTask for GetServersTask {
     async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
+        let (servers, next) = self
+            .1
+            .paginate(controller.servers.select_servers(&self.0), |server| {
+                id_key(server.id())
+            });
+
         TonicTask::new_ok(List {
-            servers: controller
-                .servers
-                .get_servers()
-                .iter()
-                .map(std::convert::Into::into)
-                .collect(),
+            servers: servers.iter().map(std::convert::Into::into).collect(),
+            next,
         })
     }
 }
 
+#[async_trait]
+impl GenericTask for RestartServersTask {
+    async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
+        let when = self.1.map(|delay| Instant::now() + delay);
+
+        let mut servers = vec![];
+        let mut requests = vec![];
+        for server in controller.servers.select_servers(&self.0) {
+            if *server.state() == State::Stopping {
+                continue;
+            }
+            requests.push(RestartRequest::new(when, server.id().clone()));
+            servers.push(Short::from(&server));
+        }
+        debug!("Scheduled restart of {} server(s)", requests.len());
+        for request in requests {
+            controller.servers.schedule_restart(request);
+        }
+
+        TonicTask::new_ok(List {
+            servers,
+            next: None,
+        })
+    }
+}
+
+#[async_trait]
+impl GenericTask for StopServersTask {
+    async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
+        let when = self.1.map(|delay| Instant::now() + delay);
+
+        let mut servers = vec![];
+        let mut requests = vec![];
+        for server in controller.servers.select_servers(&self.0) {
+            if *server.state() == State::Stopping {
+                continue;
+            }
+            requests.push(StopRequest::new(when, server.id().clone()));
+            servers.push(Short::from(&server));
+        }
+        debug!("Scheduled stop of {} server(s)", requests.len());
+        controller.servers.schedule_stops(requests);
+
+        TonicTask::new_ok(List {
+            servers,
+            next: None,
+        })
+    }
+}
+
+#[async_trait]
+impl GenericTask for SelectServersTask {
+    async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
+        let servers = controller
+            .servers
+            .select_servers(&self.0)
+            .into_iter()
+            .filter(|server| *server.state() != State::Stopping)
+            .map(|server| server.id().clone())
+            .collect::<Vec<NameAndUuid>>();
+
+        TonicTask::new_ok(servers)
+    }
+}
+
+// Bulk actions must never hit every server because a selector was left empty
+pub fn bulk_selector(
+    selector: Option<Selector>,
+    protocol: Protocol,
+) -> Result<ServerSelector, Status> {
+    let Some(selector) = selector else {
+        return Err(Status::invalid_argument("A selector is required"));
+    };
+    if protocol.has_limits()
+        && !selector.all
+        && selector.ids.is_empty()
+        && selector.group.is_none()
+        && selector.node.is_none()
+        && selector.name.is_none()
+        && !selector.empty
+        && selector.state.is_none()
+        && selector.ready.is_none()
+    {
+        return Err(Status::invalid_argument(
+            "The selector needs at least one criteria or all set to true",
+        ));
+    }
+    ServerSelector::try_from(selector)
+}
+
+impl TryFrom<Selector> for ServerSelector {
+    type Error = Status;
+
+    fn try_from(value: Selector) -> Result<Self, Self::Error> {
+        let ids = value
+            .ids
+            .iter()
+            .map(|id| Uuid::from_str(id))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| Status::invalid_argument("Invalid UUID provided"))?;
+        let name = match value.name {
+            Some(name) => Some(
+                Regex::new(&name)
+                    .map_err(|_| Status::invalid_argument("Invalid name regex provided"))?,
+            ),
+            None => None,
+        };
+        let state = match value.state {
+            Some(state) => Some(
+                common_server::State::try_from(state)
+                    .map_err(|_| Status::invalid_argument("Invalid state provided"))?
+                    .into(),
+            ),
+            None => None,
+        };
+
+        Ok(Self::new(
+            ids,
+            value.group,
+            value.node,
+            name,
+            value.empty,
+            state,
+            value.ready,
+        ))
+    }
+}
+
 impl From<&Server> for Detail {
     fn from(server: &Server) -> Self {
         Self {
@@ -96,8 +244,9 @@ impl From<&Server> for Detail {
             allocation: Some(server.allocation().into()),
             users: *server.connected_users(),
             token: server.token().clone(),
-            state: server.state().clone() as i32,
+            state: common_server::State::from(server.state()) as i32,
             ready: *server.ready(),
+            reserved: *server.reserved(),
         }
     }
 }
@@ -1,24 +1,46 @@
+use std::collections::HashSet;
+
 use anyhow::Result;
-use tonic::async_trait;
+use tonic::{Status, async_trait};
 use uuid::Uuid;
 
 use crate::{
     application::{
         Controller,
         auth::Authorization,
-        user::transfer::{Transfer, TransferTarget},
+        user::{
+            CurrentServer,
+            transfer::{ResolveError, Transfer, TransferReporter, TransferTarget},
+        },
     },
+    network::client::TransferState,
     task::{BoxedAny, GenericTask, network::TonicTask},
 };
 
-pub struct TransferUsersTask(pub Authorization, pub Vec<Uuid>, pub TransferTarget);
+pub struct TransferUsersTask(
+    pub Authorization,
+    pub Vec<Uuid>,
+    pub TransferTarget,
+    pub bool,
+    pub TransferReporter,
+);
 
 #[async_trait]
 impl GenericTask for TransferUsersTask {
     async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
-        let mut count: u32 = 0;
-        for user in &self.1 {
-            let Some(user) = controller.users.get_user_mut(user) else {
+        // A user that is listed twice would be counted twice for slots and reservations
+        let mut seen = HashSet::new();
+        self.1.retain(|uuid| seen.insert(*uuid));
+
+        if self.3 {
+            return self.transfer_together(controller).await;
+        }
+
+        // Users that wait in a queue are not counted
+        let mut started: u32 = 0;
+        for uuid in &self.1 {
+            let Some(user) = controller.users.get_user_mut(uuid) else {
+                self.reject(uuid, &ResolveError::UserNotFound.into());
                 continue;
             };
             let mut transfer = match Transfer::resolve(
@@ -27,15 +49,108 @@ impl GenericTask for TransferUsersTask {
                 &self.2,
                 &controller.servers,
                 &controller.groups,
+                &self.4,
             ) {
                 Ok(transfer) => transfer,
+                Err(ResolveError::NotServerAvailable) => {
+                    // Every server of the group is full, let the user wait for a slot
+                    if let (TransferTarget::Group(group), CurrentServer::Connected(from)) =
+                        (&self.2, user.server())
+                        && let Some(group) = controller
+                            .groups
+                            .get_group_mut(group)
+                            .filter(|group| group.accepts_queued_users(&self.2))
+                    {
+                        group
+                            .enqueue_user(*uuid, *from.uuid(), self.4.clone(), &controller.shared)
+                            .await;
+                    } else {
+                        self.reject(uuid, &ResolveError::NotServerAvailable.into());
+                    }
+                    continue;
+                }
+                Err(error) => {
+                    self.reject(uuid, &error.into());
+                    continue;
+                }
+            };
+            // Failures are reported to the caller by the transfer itself
+            let target = *transfer.to().id().uuid();
+            if Transfer::transfer_user(&mut transfer, &controller.shared)
+                .await
+                .is_err()
+            {
+                continue;
+            }
+            started += 1;
+            // Hold the slot until the user arrives
+            if let Some(server) = controller.servers.get_server_mut(&target) {
+                server.reserve_slots(1);
+            }
+        }
+        TonicTask::new_ok(started)
+    }
+}
+
+impl TransferUsersTask {
+    // Moves all users to one server. Nothing happens if a single user can not be moved
+    async fn transfer_together(&self, controller: &mut Controller) -> Result<BoxedAny> {
+        let mut sources = vec![];
+        for uuid in &self.1 {
+            let Some(user) = controller.users.get_user(uuid) else {
+                return TonicTask::new_err(ResolveError::UserNotFound.into());
+            };
+            match Transfer::check_source(&self.0, user) {
+                Ok(from) => sources.push(from.clone()),
                 Err(error) => return TonicTask::new_err(error.into()),
+            }
+        }
+
+        let to = match Transfer::resolve_target(
+            &self.2,
+            &sources.iter().map(|from| *from.uuid()).collect::<Vec<_>>(),
+            &controller.servers,
+            &controller.groups,
+            u32::try_from(self.1.len()).unwrap_or(u32::MAX),
+        ) {
+            Ok(server) => server,
+            Err(error) => return TonicTask::new_err(error.into()),
+        };
+        if to.allocation().primary_port().is_none() {
+            return TonicTask::new_err(Status::unavailable(
+                "Target server seems to have no network address",
+            ));
+        }
+
+        let target = *to.id().uuid();
+        let mut started = 0;
+        for (uuid, from) in self.1.iter().zip(sources) {
+            let Some(user) = controller.users.get_user_mut(uuid) else {
+                continue;
             };
-            if let Err(error) = Transfer::transfer_user(&mut transfer, &controller.shared).await {
-                return TonicTask::new_err(error);
+            let mut transfer = Transfer::new(Uuid::new_v4(), user, from, to, self.4.clone());
+            if Transfer::transfer_user(&mut transfer, &controller.shared)
+                .await
+                .is_ok()
+            {
+                started += 1;
             }
-            count += 1;
         }
-        TonicTask::new_ok(count)
+
+        // Every user holds a slot on the target until the transfer completes
+        if let Some(server) = controller.servers.get_server_mut(&target) {
+            server.reserve_slots(started);
+        }
+        TonicTask::new_ok(started)
+    }
+
+    fn reject(&self, user: &Uuid, error: &Status) {
+        self.4.report(
+            &Uuid::new_v4(),
+            user,
+            None,
+            TransferState::Rejected,
+            Some(error.message().to_string()),
+        );
     }
 }
@@ -3,14 +3,13 @@ use tonic::{Status, async_trait};
 use uuid::Uuid;
 
 use crate::{
-    application::{Controller, user::CurrentServer},
-    network::proto::common::common_user::{Item, List},
+    application::Controller,
+    network::proto::common::common_user::Item,
     task::{BoxedAny, GenericTask, network::TonicTask},
 };
 
 pub struct GetUserTask(pub Uuid);
 pub struct GetUserFromNameTask(pub String);
-pub struct GetUsersTask;
 pub struct UserCountTask;
 
 #[async_trait]
@@ -20,15 +19,7 @@ impl GenericTask for GetUserTask {
             return TonicTask::new_err(Status::not_found("User not found"));
         };
 
-        TonicTask::new_ok(Item {
-            name: user.id().name().clone(),
-            id: user.id().uuid().to_string(),
-            server: if let CurrentServer::Connected(server) = user.server() {
-                Some(server.uuid().to_string())
-            } else {
-                None
-            },
-        })
+        TonicTask::new_ok(Item::from(user))
     }
 }
 
@@ -39,37 +30,7 @@ impl GenericTask for GetUserFromNameTask {
             return TonicTask::new_err(Status::not_found("User not found"));
         };
 
-        TonicTask::new_ok(Item {
-            name: user.id().name().clone(),
-            id: user.id().uuid().to_string(),
-            server: if let CurrentServer::Connected(server) = user.server() {
-                Some(server.uuid().to_string())
-            } else {
-                None
-            },
-        })
-    }
-}
-
-#[async_trait]
-impl GenericTask for GetUsersTask {
-    async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
-        TonicTask::new_ok(List {
-            users: controller
-                .users
-                .get_users()
-                .iter()
-                .map(|user| Item {
-                    name: user.id().name().clone(),
-                    id: user.id().uuid().to_string(),
-                    server: if let CurrentServer::Connected(server) = user.server() {
-                        Some(server.uuid().to_string())
-                    } else {
-                        None
-                    },
-                })
-                .collect(),
-        })
+        TonicTask::new_ok(Item::from(user))
     }
 }
 
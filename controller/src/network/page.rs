@@ -0,0 +1,62 @@
+use crate::application::server::NameAndUuid;
+
+use super::{
+    handshake::Protocol,
+    proto::common::{Page, page::Order},
+};
+
+// Keeps list responses small even when a caller does not ask for pages
+const DEFAULT_PAGE_SIZE: usize = 100;
+const MAX_PAGE_SIZE: usize = 1000;
+
+// Pages are cursor based. The token is the sort key of the last item of the previous page
+pub struct Pagination {
+    size: usize,
+    token: Option<String>,
+    order: Order,
+}
+
+impl Pagination {
+    pub fn paginate<T, F>(&self, mut items: Vec<T>, key: F) -> (Vec<T>, Option<String>)
+    where
+        F: Fn(&T) -> String,
+    {
+        items.sort_by_cached_key(&key);
+        if self.order == Order::Descending {
+            items.reverse();
+        }
+
+        if let Some(token) = &self.token {
+            items.retain(|item| match self.order {
+                Order::Ascending => key(item) > *token,
+                Order::Descending => key(item) < *token,
+            });
+        }
+
+        if items.len() <= self.size {
+            return (items, None);
+        }
+        items.truncate(self.size);
+        let next = items.last().map(&key);
+        (items, next)
+    }
+}
+
+impl Pagination {
+    pub fn new(page: Option<Page>, protocol: Protocol) -> Self {
+        let value = page.unwrap_or_default();
+        Self {
+            size: match value.size as usize {
+                0 if !protocol.has_limits() => usize::MAX,
+                0 => DEFAULT_PAGE_SIZE,
+                size => size.min(MAX_PAGE_SIZE),
+            },
+            token: value.token,
+            order: Order::try_from(value.order).unwrap_or(Order::Ascending),
+        }
+    }
+}
+
+pub fn id_key(id: &NameAndUuid) -> String {
+    format!("{}/{}", id.name(), id.uuid())
+}
@@ -0,0 +1,54 @@
+use tonic::Status;
+
+use crate::{
+    application::server::{State, selector::ServerSelector},
+    network::proto::common::common_server::{self, ListReq},
+};
+
+impl TryFrom<&ListReq> for ServerSelector {
+    type Error = Status;
+
+    fn try_from(value: &ListReq) -> Result<Self, Self::Error> {
+        let state = match value.state {
+            Some(state) => Some(
+                common_server::State::try_from(state)
+                    .map_err(|_| Status::invalid_argument("Invalid state provided"))?
+                    .into(),
+            ),
+            None => None,
+        };
+
+        Ok(Self::new(
+            vec![],
+            value.group.clone(),
+            value.node.clone(),
+            None,
+            false,
+            state,
+            value.ready,
+        ))
+    }
+}
+
+// Both services encode the state with the same numbering, so this is the only mapping
+impl From<common_server::State> for State {
+    fn from(value: common_server::State) -> Self {
+        match value {
+            common_server::State::Starting | common_server::State::Preparing => State::Starting,
+            common_server::State::Restarting => State::Restarting,
+            common_server::State::Running => State::Running,
+            common_server::State::Stopping => State::Stopping,
+        }
+    }
+}
+
+impl From<&State> for common_server::State {
+    fn from(value: &State) -> Self {
+        match value {
+            State::Starting => common_server::State::Starting,
+            State::Restarting => common_server::State::Restarting,
+            State::Running => common_server::State::Running,
+            State::Stopping => common_server::State::Stopping,
+        }
+    }
+}
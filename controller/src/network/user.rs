@@ -0,0 +1,90 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+use tonic::{Request, Status, async_trait};
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        Controller,
+        user::{CurrentServer, User},
+    },
+    network::{
+        handshake::Protocol,
+        page::{Pagination, id_key},
+        proto::common::common_user::{Item, List, ListReq},
+    },
+    task::{BoxedAny, GenericTask, network::TonicTask},
+};
+
+// Both services list users the same way
+pub struct GetUsersTask(pub Option<Uuid>, pub Option<String>, pub Pagination);
+
+impl TryFrom<Request<ListReq>> for GetUsersTask {
+    type Error = Status;
+
+    fn try_from(request: Request<ListReq>) -> Result<Self, Self::Error> {
+        let protocol = Protocol::of(&request);
+        let value = request.into_inner();
+        let server = match value.server {
+            Some(server) => Some(
+                Uuid::from_str(&server)
+                    .map_err(|_| Status::invalid_argument("Invalid UUID provided"))?,
+            ),
+            None => None,
+        };
+        Ok(Self(
+            server,
+            value.group,
+            Pagination::new(value.page, protocol),
+        ))
+    }
+}
+
+#[async_trait]
+impl GenericTask for GetUsersTask {
+    async fn run(&mut self, controller: &mut Controller) -> Result<BoxedAny> {
+        let users = controller
+            .users
+            .get_users()
+            .into_iter()
+            .filter(|user| {
+                if self.0.is_none() && self.1.is_none() {
+                    return true;
+                }
+                // Users that are transfering are not on any server right now
+                let CurrentServer::Connected(server) = user.server() else {
+                    return false;
+                };
+                self.0.as_ref().is_none_or(|uuid| server.uuid() == uuid)
+                    && self.1.as_ref().is_none_or(|group| {
+                        controller
+                            .servers
+                            .get_server(server.uuid())
+                            .and_then(|server| server.group().as_ref())
+                            == Some(group)
+                    })
+            })
+            .collect();
+        let (users, next) = self.2.paginate(users, |user| id_key(user.id()));
+
+        TonicTask::new_ok(List {
+            users: users.into_iter().map(Into::into).collect(),
+            next,
+        })
+    }
+}
+
+impl From<&User> for Item {
+    fn from(user: &User) -> Self {
+        Self {
+            name: user.id().name().clone(),
+            id: user.id().uuid().to_string(),
+            server: if let CurrentServer::Connected(server) = user.server() {
+                Some(server.uuid().to_string())
+            } else {
+                None
+            },
+        }
+    }
+}
@@ -5,17 +5,19 @@ This makes it easier to change them in the future
 
 use std::{
     borrow::Cow,
-    path::{Path, PathBuf},
+    path::{Component, Path, PathBuf},
 };
 
 use anyhow::Result;
 use serde::{Serialize, de::DeserializeOwned};
 use simplelog::warn;
 use tokio::fs;
+use uuid::Uuid;
 
 /* Logs */
 const LOGS_DIRECTORY: &str = "logs";
 const LATEST_LOG_FILE: &str = "latest.log";
+const SERVER_LOGS_DIRECTORY: &str = "servers";
 
 /* Nodes */
 const NODES_DIRECTORY: &str = "nodes";
@@ -26,6 +28,9 @@ const GROUPS_DIRECTORY: &str = "groups";
 /* Auth */
 const USERS_DIRECTORY: &str = "users";
 
+/* Sessions */
+const SESSIONS_DIRECTORY: &str = "sessions";
+
 /* Configs */
 const CONFIG_DIRECTORY: &str = "configs";
 const PRIMARY_CONFIG_FILE: &str = "config.toml";
@@ -50,6 +55,14 @@ impl Storage {
     pub fn latest_log_file() -> PathBuf {
         PathBuf::from(LOGS_DIRECTORY).join(LATEST_LOG_FILE)
     }
+    pub fn server_logs_directory() -> PathBuf {
+        PathBuf::from(LOGS_DIRECTORY).join(SERVER_LOGS_DIRECTORY)
+    }
+    pub fn server_log_directory(name: &str, uuid: &Uuid) -> PathBuf {
+        Storage::server_logs_directory()
+            .join(name)
+            .join(uuid.to_string())
+    }
 
     /* Nodes */
     pub fn nodes_directory() -> PathBuf {
@@ -75,6 +88,14 @@ impl Storage {
         Storage::users_directory().join(format!("{name}.toml"))
     }
 
+    /* Sessions */
+    pub fn sessions_directory() -> PathBuf {
+        PathBuf::from(SESSIONS_DIRECTORY)
+    }
+    pub fn session_log_file(day: u64) -> PathBuf {
+        Storage::sessions_directory().join(format!("{day}.jsonl"))
+    }
+
     /* Configs */
     pub fn configs_directory() -> PathBuf {
         PathBuf::from(CONFIG_DIRECTORY)
@@ -106,6 +127,9 @@ impl Storage {
     pub fn plugins_directory() -> PathBuf {
         PathBuf::from(PLUGINS_DIRECTORY)
     }
+    pub fn wasm_plugin_file(name: &str) -> PathBuf {
+        Storage::plugins_directory().join(format!("{name}.wasm"))
+    }
     pub fn data_directory_for_plugin<'a, T>(name: T) -> PathBuf
     where
         T: Into<Cow<'a, str>>,
@@ -119,6 +143,15 @@ impl Storage {
         Storage::configs_directory().join(name.into().into_owned())
     }
 
+    // Names from requests are joined onto directories, so they have to stay a single component
+    pub fn is_valid_name(name: &str) -> bool {
+        let mut components = Path::new(name).components();
+        matches!(
+            (components.next(), components.next()),
+            (Some(Component::Normal(component)), None) if component.to_str() == Some(name)
+        )
+    }
+
     pub async fn for_each_content(path: &Path) -> Result<Vec<(PathBuf, String, String)>> {
         let mut result = Vec::new();
         let mut directory = fs::read_dir(path).await?;
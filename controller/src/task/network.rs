@@ -10,6 +10,7 @@ use tonic::{Request, Status};
 
 use crate::{
     application::auth::{AuthType, Authorization, permissions::Permissions},
+    network::handshake::Protocol,
     task::Task,
 };
 
@@ -23,6 +24,7 @@ pub struct TonicTask;
 impl TonicTask {
     #[allow(clippy::result_large_err)]
     pub fn get_auth<T>(auth: AuthType, request: &Request<T>) -> Result<Authorization, Status> {
+        Protocol::of(request).check()?;
         match request.extensions().get::<Authorization>() {
             Some(data) if data.is_type(auth) => Ok(data.clone()),
             _ => Err(Status::unauthenticated("Not linked")),
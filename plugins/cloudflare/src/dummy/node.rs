@@ -32,4 +32,8 @@ impl GuestNode for Node {
     fn stop(&self, _: Server, _: Guard) {
         unimplemented!()
     }
+
+    fn adopt(&self, _: Server) -> Result<ScreenType, ErrorMessage> {
+        unimplemented!()
+    }
 }
@@ -7,37 +7,44 @@ use crate::{
     error,
     generated::{
         exports::plugin::system::event::GuestListener,
-        plugin::system::{data_types::Server, types::ErrorMessage},
+        plugin::system::{
+            data_types::{ChannelMessage, Identity, Server},
+            types::ErrorMessage,
+        },
     },
     plugin::{batcher::Batcher, config::Entry},
 };
 
+// Shared with the plugin so config changes reach the listener
+pub type Entries = Rc<RefCell<Vec<(Regex, Entry)>>>;
+
 pub struct Listener {
     /* Configuration */
-    entries: Vec<(Regex, Entry)>,
+    entries: Entries,
 
     /* Batcher */
     batcher: Rc<RefCell<Batcher>>,
 }
 
 impl Listener {
-    pub fn new(entries: &[Entry], batcher: Rc<RefCell<Batcher>>) -> Self {
-        Self {
-            entries: entries
-                .iter()
-                .filter_map(|entry| match Regex::new(&entry.servers) {
-                    Ok(servers) => Some((servers, entry.clone())),
-                    Err(error) => {
-                        error!(
-                            "Failed to compile regex({}) for entry({}): {}",
-                            entry.servers, entry.name, error
-                        );
-                        None
-                    }
-                })
-                .collect(),
-            batcher,
-        }
+    pub fn new(entries: Entries, batcher: Rc<RefCell<Batcher>>) -> Self {
+        Self { entries, batcher }
+    }
+
+    pub fn compile(entries: &[Entry]) -> Vec<(Regex, Entry)> {
+        entries
+            .iter()
+            .filter_map(|entry| match Regex::new(&entry.servers) {
+                Ok(servers) => Some((servers, entry.clone())),
+                Err(error) => {
+                    error!(
+                        "Failed to compile regex({}) for entry({}): {}",
+                        entry.servers, entry.name, error
+                    );
+                    None
+                }
+            })
+            .collect()
     }
 }
 
@@ -47,7 +54,7 @@ impl GuestListener for Listener {
     }
 
     fn server_stop(&self, server: Server) -> Result<(), ErrorMessage> {
-        for (regex, entry) in &self.entries {
+        for (regex, entry) in self.entries.borrow().iter() {
             if regex.is_match(&server.name) {
                 self.batcher.borrow_mut().delete(entry.clone(), server.uuid);
                 break;
@@ -62,7 +69,7 @@ impl GuestListener for Listener {
             return Ok(());
         }
 
-        for (regex, entry) in &self.entries {
+        for (regex, entry) in self.entries.borrow().iter() {
             if regex.is_match(&server.name) {
                 self.batcher.borrow_mut().create(entry.clone(), server);
                 break;
@@ -70,4 +77,32 @@ impl GuestListener for Listener {
         }
         Ok(())
     }
+
+    fn user_connected(&self, _: Identity, _: Server) -> Result<(), ErrorMessage> {
+        unimplemented!()
+    }
+
+    fn user_disconnected(&self, _: Identity, _: Server) -> Result<(), ErrorMessage> {
+        unimplemented!()
+    }
+
+    fn user_transferred(&self, _: Identity, _: Identity, _: Server) -> Result<(), ErrorMessage> {
+        unimplemented!()
+    }
+
+    fn group_change_active(&self, _: String, _: bool) -> Result<(), ErrorMessage> {
+        unimplemented!()
+    }
+
+    fn group_scaled(&self, _: String, _: u32) -> Result<(), ErrorMessage> {
+        unimplemented!()
+    }
+
+    fn node_change_active(&self, _: String, _: bool) -> Result<(), ErrorMessage> {
+        unimplemented!()
+    }
+
+    fn channel_message(&self, _: ChannelMessage) -> Result<(), ErrorMessage> {
+        unimplemented!()
+    }
 }
@@ -17,13 +17,14 @@ use crate::{
         exports::plugin::system::{
             bridge::{
                 Capabilities, ErrorMessage, GuestPlugin, Information, Listener as GenericListener,
-                Node as GenericNode, ScopedErrors,
+                Node as GenericNode, Schema, ScopedErrors,
             },
             event::Events,
         },
         plugin::system::data_types::Features,
     },
-    listener::Listener,
+    info,
+    listener::{Entries, Listener},
 };
 
 pub mod backend;
@@ -41,6 +42,7 @@ pub const FEATURES: Features = Features::LISTENER;
 pub struct Cloudflare {
     /* Configuration */
     config: RefCell<Config>,
+    entries: Entries,
 
     /* Batcher */
     batcher: Rc<RefCell<Batcher>>,
@@ -57,6 +59,7 @@ impl GuestPlugin for Cloudflare {
     fn new(_: String) -> Self {
         Self {
             config: RefCell::new(Config::default()), // Dummy config
+            entries: Rc::new(RefCell::new(vec![])),
             batcher: Rc::new(RefCell::new(Batcher::default())),
             backend: RefCell::new(Backend::default()),
             records: RefCell::new(Records::default()),
@@ -71,6 +74,7 @@ impl GuestPlugin for Cloudflare {
                 let config = Config::parse()?;
                 own.backend.replace(Backend::new(&config));
                 own.records.replace(Records::new(&config));
+                own.entries.replace(Listener::compile(&config.entries));
                 own.config.replace(config);
             }
             Ok(())
@@ -92,10 +96,7 @@ impl GuestPlugin for Cloudflare {
     fn init_listener(&self) -> (Events, GenericListener) {
         (
             Events::SERVER_STOP | Events::SERVER_CHANGE_READY,
-            GenericListener::new(Listener::new(
-                &self.config.borrow().entries,
-                self.batcher.clone(),
-            )),
+            GenericListener::new(Listener::new(self.entries.clone(), self.batcher.clone())),
         )
     }
 
@@ -126,4 +127,29 @@ impl GuestPlugin for Cloudflare {
         self.records.borrow_mut().shutdown(&self.backend.borrow());
         Ok(())
     }
+
+    fn config_schema(&self) -> Option<Schema> {
+        Some(Config::schema())
+    }
+
+    fn validate_config(&self, content: String) -> Result<(), ErrorMessage> {
+        Config::from_content(&content)
+            .map(|_| ())
+            .map_err(|error| error.to_string())
+    }
+
+    fn on_config_changed(&self, content: String) -> Result<(), ErrorMessage> {
+        let config = Config::from_content(&content).map_err(|error| error.to_string())?;
+
+        // Records of removed entries are deleted with the old token. Servers that are already
+        // ready only receive records of new entries once their ready state changes again
+        self.records
+            .borrow_mut()
+            .reload(&config, &self.backend.borrow());
+        self.backend.replace(Backend::new(&config));
+        self.entries.replace(Listener::compile(&config.entries));
+        self.config.replace(config);
+        info!("Applied the new configuration");
+        Ok(())
+    }
 }
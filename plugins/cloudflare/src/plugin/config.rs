@@ -3,11 +3,15 @@ use std::{
     hash::{Hash, Hasher},
 };
 
-use anyhow::Result;
+use anyhow::{Result, anyhow, bail};
 use common::file::SyncLoadFromTomlFile;
+use regex::Regex;
 use serde::Deserialize;
 
-use crate::storage::Storage;
+use crate::{
+    generated::plugin::system::config::{Field, Kind, Schema},
+    storage::Storage,
+};
 
 const DEFAULT_CONFIG: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/configs/config.toml"));
@@ -52,6 +56,50 @@ impl Config {
         }
         Self::from_file(&path)
     }
+
+    pub fn from_content(content: &str) -> Result<Self> {
+        let config: Self = toml::from_str(content)?;
+        if config.rate == 0 {
+            bail!("The rate must be at least one update per minute");
+        }
+        for entry in &config.entries {
+            Regex::new(&entry.servers).map_err(|error| {
+                anyhow!(
+                    "Invalid regex({}) for entry({}): {}",
+                    entry.servers,
+                    entry.name,
+                    error
+                )
+            })?;
+        }
+        Ok(config)
+    }
+
+    pub fn schema() -> Schema {
+        let field = |path: &str, kind, description: &str| Field {
+            path: path.to_string(),
+            kind,
+            description: description.to_string(),
+            required: true,
+        };
+
+        Schema {
+            file: Storage::primary_config_name(),
+            fields: vec![
+                field(
+                    "rate",
+                    Kind::Integer,
+                    "How often the DNS records are updated per minute",
+                ),
+                field("account.token", Kind::String, "The Cloudflare API token"),
+                field(
+                    "entries",
+                    Kind::Array,
+                    "The SRV records to maintain and the servers that receive them",
+                ),
+            ],
+        }
+    }
 }
 
 impl Hash for Weight {
@@ -155,6 +155,49 @@ impl Records {
         }
     }
 
+    // Keeps the records of entries that still exist and deletes the others
+    pub fn reload(&mut self, config: &Config, backend: &Backend) {
+        let mut records = Self::new(config);
+
+        let mut count = 0;
+        for (zone_id, zone) in &mut self.zones {
+            let mut batch = BBatch::default();
+
+            for (entry, record_map) in zone.records.drain() {
+                if let Some(kept) = records
+                    .zones
+                    .get_mut(zone_id)
+                    .and_then(|zone| zone.records.get_mut(&entry))
+                {
+                    *kept = record_map;
+                    continue;
+                }
+
+                for record in record_map.values() {
+                    // If this record is a placeholder we skip it.
+                    if record.id.is_none() {
+                        continue;
+                    }
+
+                    batch.deletes.push(BDelete::from(record));
+                }
+            }
+
+            if batch.deletes.is_empty() {
+                // No request to cloudflare required
+                continue;
+            }
+
+            count += batch.deletes.len();
+            backend.send_batch(zone_id, &batch);
+        }
+
+        if count > 0 {
+            info!("Deleted {} records of removed entries...", count);
+        }
+        *self = records;
+    }
+
     pub fn shutdown(&mut self, backend: &Backend) {
         info!("Deleting created records...");
 
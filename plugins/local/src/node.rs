@@ -117,4 +117,12 @@ impl GuestNode for Node {
     fn stop(&self, server: Server, guard: Guard) {
         self.0.servers.borrow_mut().stop(&self.0, &server, guard);
     }
+
+    // The processes belong to the previous instance and end together with it
+    fn adopt(&self, server: Server) -> Result<ScreenType, ErrorMessage> {
+        Err(format!(
+            "Server {} runs as a process of the previous plugin instance and can not be taken over. Stop it before reloading the plugin",
+            server.name
+        ))
+    }
 }
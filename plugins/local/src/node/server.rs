@@ -14,6 +14,7 @@ use crate::{
             data_types::DiskRetention,
             file::remove_dir_all,
             process::{ExitStatus, Process, ProcessBuilder},
+            screen_events::output_available,
             tls::get_certificate,
         },
     },
@@ -187,6 +188,11 @@ impl Server {
             }
             _ => State::Running,
         };
+
+        // Let the controller pull the screen only when there is something to read
+        if self.process.has_output() {
+            output_available(&self.request.uuid);
+        }
         Ok(&self.state)
     }
 
@@ -10,7 +10,7 @@ use crate::{
         exports::plugin::system::{
             bridge::{
                 Capabilities, ErrorMessage, GuestPlugin, Information, Listener as GenericListener,
-                Node as GenericNode, ScopedErrors,
+                Node as GenericNode, Schema, ScopedErrors,
             },
             event::Events,
         },
@@ -123,4 +123,26 @@ impl GuestPlugin for Local {
     fn shutdown(&self) -> Result<(), ScopedErrors> {
         Ok(())
     }
+
+    fn config_schema(&self) -> Option<Schema> {
+        Some(Config::schema())
+    }
+
+    fn validate_config(&self, content: String) -> Result<(), ErrorMessage> {
+        Config::from_content(&content)
+            .map(|_| ())
+            .map_err(|error| error.to_string())
+    }
+
+    fn on_config_changed(&self, content: String) -> Result<(), ErrorMessage> {
+        let config = Config::from_content(&content).map_err(|error| error.to_string())?;
+
+        // Ports that are in use stay allocated until their servers stop
+        if config.range() != self.config.borrow().range() {
+            self.allocator.borrow_mut().resize(config.range().clone());
+        }
+        self.config.replace(config);
+        info!("Applied the new configuration");
+        Ok(())
+    }
 }
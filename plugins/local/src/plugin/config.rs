@@ -1,10 +1,13 @@
 use std::{collections::HashMap, fs, ops::Range, time::Duration};
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 use common::file::SyncLoadFromTomlFile;
 use serde::Deserialize;
 
-use crate::storage::Storage;
+use crate::{
+    generated::plugin::system::config::{Field, Kind, Schema},
+    storage::Storage,
+};
 
 const DEFAULT_CONFIG: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/configs/config.toml"));
@@ -45,6 +48,54 @@ impl Config {
         Self::from_file(&path)
     }
 
+    pub fn from_content(content: &str) -> Result<Self> {
+        let config: Self = toml::from_str(content)?;
+        if config.range().is_empty() {
+            bail!("The port range must contain at least one port");
+        }
+        Ok(config)
+    }
+
+    pub fn schema() -> Schema {
+        let field = |path: &str, kind, description: &str| Field {
+            path: path.to_string(),
+            kind,
+            description: description.to_string(),
+            required: true,
+        };
+
+        Schema {
+            file: Storage::primary_config_name(),
+            fields: vec![
+                field(
+                    "network.host",
+                    Kind::String,
+                    "The host address that users can use to reach servers",
+                ),
+                field(
+                    "network.ports.range",
+                    Kind::Table,
+                    "The range of ports used to start servers",
+                ),
+                field(
+                    "network.ports.mappings",
+                    Kind::Table,
+                    "Fixed ports for servers by name",
+                ),
+                field(
+                    "timeouts.stop",
+                    Kind::Table,
+                    "How long to wait for a server to stop before killing it",
+                ),
+                field(
+                    "timeouts.restart",
+                    Kind::Table,
+                    "How long to wait for a server to stop before killing and respawning it",
+                ),
+            ],
+        }
+    }
+
     pub fn host(&self) -> &str {
         &self.network.host
     }
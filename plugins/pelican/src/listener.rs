@@ -1,6 +1,9 @@
 use crate::generated::{
     exports::plugin::system::event::GuestListener,
-    plugin::system::{data_types::Server, types::ErrorMessage},
+    plugin::system::{
+        data_types::{ChannelMessage, Identity, Server},
+        types::ErrorMessage,
+    },
 };
 
 pub struct Listener();
@@ -17,4 +20,32 @@ impl GuestListener for Listener {
     fn server_change_ready(&self, _: Server, _: bool) -> Result<(), ErrorMessage> {
         unimplemented!()
     }
+
+    fn user_connected(&self, _: Identity, _: Server) -> Result<(), ErrorMessage> {
+        unimplemented!()
+    }
+
+    fn user_disconnected(&self, _: Identity, _: Server) -> Result<(), ErrorMessage> {
+        unimplemented!()
+    }
+
+    fn user_transferred(&self, _: Identity, _: Identity, _: Server) -> Result<(), ErrorMessage> {
+        unimplemented!()
+    }
+
+    fn group_change_active(&self, _: String, _: bool) -> Result<(), ErrorMessage> {
+        unimplemented!()
+    }
+
+    fn group_scaled(&self, _: String, _: u32) -> Result<(), ErrorMessage> {
+        unimplemented!()
+    }
+
+    fn node_change_active(&self, _: String, _: bool) -> Result<(), ErrorMessage> {
+        unimplemented!()
+    }
+
+    fn channel_message(&self, _: ChannelMessage) -> Result<(), ErrorMessage> {
+        unimplemented!()
+    }
 }
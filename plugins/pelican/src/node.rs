@@ -97,4 +97,8 @@ impl GuestNode for Node {
     fn stop(&self, server: Server, guard: Guard) {
         self.0.servers.borrow_mut().stop(&self.0, &server, guard);
     }
+
+    fn adopt(&self, server: Server) -> Result<ScreenType, ErrorMessage> {
+        self.0.servers.borrow_mut().adopt(&self.0, server)
+    }
 }
@@ -67,6 +67,14 @@ impl AllocationManager {
             .collect()
     }
 
+    // Marks the addresses of a server that a previous instance of the plugin created as used
+    pub fn adopt(&mut self, node: &InnerNode, identifier: &str) {
+        let (primary, allocations) = node.backend.get_allocations_by_server(identifier);
+        for address in std::iter::once(&primary).chain(&allocations) {
+            self.allocations.insert(address.port, address.into());
+        }
+    }
+
     pub fn free(&mut self, addresses: Vec<Address>) {
         for address in addresses {
             if self.allocations.remove(&address.port).is_none() {
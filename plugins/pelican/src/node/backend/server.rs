@@ -167,4 +167,25 @@ impl Backend {
             },
         )
     }
+
+    // Temporary servers carry their creation time in the name, so only the prefix is known
+    pub fn get_servers_by_prefix(&self, prefix: &str) -> Vec<BServer> {
+        let mut servers = vec![];
+        self.for_each_on_pages::<BServer>(
+            Method::Get,
+            &Endpoint::Application,
+            "servers",
+            |object| {
+                servers.extend(
+                    object
+                        .data
+                        .iter()
+                        .filter(|server| server.attributes.name.starts_with(prefix))
+                        .map(|server| server.attributes.clone()),
+                );
+                false
+            },
+        );
+        servers
+    }
 }
@@ -79,34 +79,7 @@ impl Server {
         }
 
         // Build egg from request
-        let egg = {
-            let mut id = None;
-            let mut startup = None;
-            for value in &request.allocation.specification.settings {
-                match value.0.as_str() {
-                    "egg" => match value.1.parse::<u32>() {
-                        Ok(value) => {
-                            id = Some(value);
-                        }
-                        Err(_) => {
-                            error!("The egg setting must be a number!");
-                        }
-                    },
-                    "startup" => {
-                        startup = Some(value.1.clone());
-                    }
-                    _ => {}
-                }
-            }
-
-            if id.is_none() {
-                bail!("The following required settings to start the server are missing: egg");
-            }
-            BServerEgg {
-                id: id.unwrap(),
-                startup,
-            }
-        };
+        let egg = Self::egg(&request)?;
 
         // Prepare the environment
         let mut environment = request.allocation.specification.environment.clone();
@@ -126,6 +99,73 @@ impl Server {
         }
     }
 
+    // Takes over a server that a previous instance of the plugin created on the panel
+    pub fn adopt(node: &InnerNode, request: bridge::Server) -> Result<Self> {
+        let name = TimedName::new(&node.identifier, &request.name, true);
+        let server = if matches!(
+            request.allocation.specification.disk_retention,
+            DiskRetention::Permanent
+        ) {
+            node.backend.get_server_by_name(&name)
+        } else {
+            node.backend
+                .get_servers_by_prefix(&format!("{}#", name.get_name()))
+                .into_iter()
+                .max_by_key(|server| {
+                    server
+                        .name
+                        .rsplit('#')
+                        .next()
+                        .and_then(|timestamp| timestamp.parse::<u64>().ok())
+                })
+        };
+        let Some(server) = server else {
+            bail!("Server {} does not exist on the panel", request.name);
+        };
+
+        node.allocations
+            .borrow_mut()
+            .adopt(node, &server.identifier);
+        Ok(Self {
+            name: TimedName::from_existing(&request.name, server.name.clone()),
+            egg: Self::egg(&request)?,
+            request,
+            backend: (server.id, server.identifier),
+            last_update: Instant::now(),
+            state: State::Running,
+            guard: None,
+        })
+    }
+
+    fn egg(request: &bridge::Server) -> Result<BServerEgg> {
+        let mut id = None;
+        let mut startup = None;
+        for value in &request.allocation.specification.settings {
+            match value.0.as_str() {
+                "egg" => match value.1.parse::<u32>() {
+                    Ok(value) => {
+                        id = Some(value);
+                    }
+                    Err(_) => {
+                        error!("The egg setting must be a number!");
+                    }
+                },
+                "startup" => {
+                    startup = Some(value.1.clone());
+                }
+                _ => {}
+            }
+        }
+
+        if id.is_none() {
+            bail!("The following required settings to start the server are missing: egg");
+        }
+        Ok(BServerEgg {
+            id: id.unwrap(),
+            startup,
+        })
+    }
+
     fn update(
         node: &InnerNode,
         request: bridge::Server,
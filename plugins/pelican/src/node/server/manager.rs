@@ -61,6 +61,17 @@ impl ServerManager {
         screen
     }
 
+    pub fn adopt(&mut self, node: &InnerNode, request: bridge::Server) -> Result<ScreenType, String> {
+        let name = request.name.clone();
+
+        let server = Server::adopt(node, request).map_err(|error| error.to_string())?;
+        let screen = server.screen(node.config.borrow().url());
+
+        info!("Server {} taken over from the previous plugin instance", name);
+        self.servers.insert(name, server);
+        Ok(screen)
+    }
+
     pub fn restart(&mut self, node: &InnerNode, server: &bridge::Server) {
         let Some(server) = self.servers.get_mut(&server.name) else {
             error!("Server not found while restarting server {}", server.name);
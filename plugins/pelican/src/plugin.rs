@@ -9,7 +9,7 @@ use crate::{
         exports::plugin::system::{
             bridge::{
                 Capabilities, ErrorMessage, GuestPlugin, Information, Listener as GenericListener,
-                Node as GenericNode, ScopedErrors,
+                Node as GenericNode, Schema, ScopedErrors,
             },
             event::Events,
         },
@@ -17,6 +17,7 @@ use crate::{
     },
     info,
     node::{InnerNode, Node, backend::Backend},
+    warn,
 };
 
 pub mod config;
@@ -108,4 +109,26 @@ impl GuestPlugin for Pelican {
     fn shutdown(&self) -> Result<(), ScopedErrors> {
         Ok(())
     }
+
+    fn config_schema(&self) -> Option<Schema> {
+        Some(Config::schema())
+    }
+
+    fn validate_config(&self, content: String) -> Result<(), ErrorMessage> {
+        Config::from_content(&content)
+            .map(|_| ())
+            .map_err(|error| error.to_string())
+    }
+
+    fn on_config_changed(&self, content: String) -> Result<(), ErrorMessage> {
+        let config = Config::from_content(&content).map_err(|error| error.to_string())?;
+
+        // Nodes keep the panel connection they were initialized with
+        if config.changes_connection(&self.config.borrow()) {
+            warn!("Panel connection settings only apply to nodes that are initialized afterwards");
+        }
+        self.config.replace(config);
+        info!("Applied the new configuration");
+        Ok(())
+    }
 }
@@ -5,7 +5,10 @@ use common::file::SyncLoadFromTomlFile;
 use serde::Deserialize;
 use url::Url;
 
-use crate::storage::Storage;
+use crate::{
+    generated::plugin::system::config::{Field, Kind, Schema},
+    storage::Storage,
+};
 
 const DEFAULT_CONFIG: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/configs/config.toml"));
@@ -54,6 +57,59 @@ impl Config {
         }
     }
 
+    pub fn from_content(content: &str) -> Result<Self> {
+        Ok(toml::from_str(content)?)
+    }
+
+    pub fn schema() -> Schema {
+        let field = |path: &str, kind, description: &str| Field {
+            path: path.to_string(),
+            kind,
+            description: description.to_string(),
+            required: true,
+        };
+
+        Schema {
+            file: Storage::primary_config_name(),
+            fields: vec![
+                field("network.url", Kind::String, "The url of the pelican panel"),
+                field(
+                    "application.token",
+                    Kind::String,
+                    "The token used for the application API",
+                ),
+                field(
+                    "user.username",
+                    Kind::String,
+                    "The user used for the client API",
+                ),
+                field(
+                    "user.token",
+                    Kind::String,
+                    "The token used for the client API",
+                ),
+                field(
+                    "timeouts.stop",
+                    Kind::Table,
+                    "How long to wait for a server to stop before killing it",
+                ),
+                field(
+                    "timeouts.restart",
+                    Kind::Table,
+                    "How long to wait for a server to stop before killing and respawning it",
+                ),
+            ],
+        }
+    }
+
+    // True if nodes would connect differently with this config
+    pub fn changes_connection(&self, other: &Config) -> bool {
+        self.url() != other.url()
+            || self.token() != other.token()
+            || self.username() != other.username()
+            || self.user_token() != other.user_token()
+    }
+
     pub fn url(&self) -> &Url {
         &self.network.url
     }
@@ -14,4 +14,8 @@ impl Storage {
     pub fn primary_config_file() -> PathBuf {
         Storage::configs_directory().join(PRIMARY_CONFIG_FILE)
     }
+    // Relative to the configs directory. This is how the controller refers to it
+    pub fn primary_config_name() -> String {
+        PRIMARY_CONFIG_FILE.to_string()
+    }
 }